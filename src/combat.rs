@@ -0,0 +1,194 @@
+use std::{str::FromStr, time::Duration};
+
+use clap::ValueEnum;
+
+use crate::{costs::GAME_TICK, data};
+
+/// Time between kills spent finding the next target and waiting for respawns, TODO: Made up
+const KILL_OVERHEAD: Duration = Duration::from_millis(2400);
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KillTimeModel {
+    /// Use the hand-measured kill time of every monster
+    #[default]
+    Measured,
+    /// Experimental: derive kill times from combat levels and equipment. Only monsters and superiors with known
+    /// defences use it, which are just the giants so far, and everything else keeps its measured time
+    Combat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombatLevels {
+    pub attack: u8,
+    pub strength: u8,
+    pub defence: u8,
+    pub hitpoints: u8,
+}
+
+impl Default for CombatLevels {
+    fn default() -> Self {
+        Self {
+            attack: 1,
+            strength: 1,
+            defence: 1,
+            hitpoints: 10,
+        }
+    }
+}
+
+impl CombatLevels {
+    pub fn from_exp(exp: &CombatExp) -> Self {
+        Self {
+            attack: data::level_for_exp(exp.attack),
+            strength: data::level_for_exp(exp.strength),
+            defence: data::level_for_exp(exp.defence),
+            hitpoints: data::level_for_exp(exp.hitpoints),
+        }
+    }
+
+    /// Combat level, assuming level 1 prayer, ranged and magic
+    pub fn combat_level(&self) -> u8 {
+        let base = (self.defence as f32 + self.hitpoints as f32) / 4.0;
+        let melee = 0.325 * (self.attack as f32 + self.strength as f32);
+        (base + melee) as u8
+    }
+}
+
+/// Parsed from the levels separated by commas, like 50,60,1,60 for attack, strength, defence and hitpoints
+impl FromStr for CombatLevels {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let levels = s
+            .split(',')
+            .map(|level| {
+                let level = level.trim();
+                match level.parse() {
+                    Ok(level @ 1..=99) => Ok(level),
+                    _ => Err(format!("Invalid combat level '{}'", level)),
+                }
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let [attack, strength, defence, hitpoints] = levels[..] else {
+            return Err(format!(
+                "Expected attack,strength,defence,hitpoints levels, got '{}'",
+                s
+            ));
+        };
+        Ok(Self {
+            attack,
+            strength,
+            defence,
+            hitpoints,
+        })
+    }
+}
+
+/// The melee skill that gets the combat exp from each kill
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CombatSkill {
+    Attack,
+    #[default]
+    Strength,
+    Defence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombatExp {
+    pub attack: u32,
+    pub strength: u32,
+    pub defence: u32,
+    pub hitpoints: u32,
+}
+
+impl Default for CombatExp {
+    fn default() -> Self {
+        Self::from_levels(&CombatLevels::default())
+    }
+}
+
+impl CombatExp {
+    pub fn from_levels(levels: &CombatLevels) -> Self {
+        Self {
+            attack: data::exp_for_level(levels.attack),
+            strength: data::exp_for_level(levels.strength),
+            defence: data::exp_for_level(levels.defence),
+            hitpoints: data::exp_for_level(levels.hitpoints),
+        }
+    }
+
+    /// Add the exp for dealing `damage` with melee, while training `skill`
+    pub fn add_damage(&mut self, damage: u64, skill: CombatSkill) {
+        let exp = (4 * damage) as u32;
+        match skill {
+            CombatSkill::Attack => self.attack += exp,
+            CombatSkill::Strength => self.strength += exp,
+            CombatSkill::Defence => self.defence += exp,
+        }
+        self.hitpoints += (4 * damage / 3) as u32;
+    }
+}
+
+/// Melee bonuses of all worn equipment combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Equipment {
+    pub attack_bonus: i32,
+    pub strength_bonus: i32,
+    pub attack_speed: u32, // In game ticks
+}
+
+impl Default for Equipment {
+    fn default() -> Self {
+        // Unarmed
+        Self {
+            attack_bonus: 0,
+            strength_bonus: 0,
+            attack_speed: 4,
+        }
+    }
+}
+
+/// Combat stats of a monster, against the attack style that is used on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonsterDefence {
+    pub hitpoints: u32,
+    pub defence_level: u32,
+    pub defence_bonus: i32,
+}
+
+/// Melee max hit on the aggressive style, without prayers
+pub fn max_hit(levels: &CombatLevels, equipment: &Equipment) -> u32 {
+    let effective_strength = levels.strength as i32 + 3 + 8;
+    ((effective_strength * (equipment.strength_bonus + 64)) as f32 / 640.0 + 0.5).max(1.0) as u32
+}
+
+/// Chance of each attack hitting, on the aggressive style, without prayers
+pub fn hit_chance(levels: &CombatLevels, equipment: &Equipment, defence: &MonsterDefence) -> f32 {
+    let effective_attack = levels.attack as i32 + 8;
+    let attack_roll = (effective_attack * (equipment.attack_bonus + 64)).max(0) as f32;
+    let defence_roll =
+        ((defence.defence_level as i32 + 9) * (defence.defence_bonus + 64)).max(0) as f32;
+    if attack_roll > defence_roll {
+        1.0 - (defence_roll + 2.0) / (2.0 * (attack_roll + 1.0))
+    } else {
+        attack_roll / (2.0 * (defence_roll + 1.0))
+    }
+}
+
+/// Expected time to kill a single monster, including the time between kills
+pub fn kill_time(
+    levels: &CombatLevels,
+    equipment: &Equipment,
+    defence: &MonsterDefence,
+) -> Duration {
+    // Damage is uniformly distributed between 0 and the max hit, ignoring overkill
+    let damage_per_attack =
+        hit_chance(levels, equipment, defence) * max_hit(levels, equipment) as f32 / 2.0;
+    assert!(
+        damage_per_attack > 0.0,
+        "Cannot damage a monster with {} defence",
+        defence.defence_level
+    );
+    let attacks = defence.hitpoints as f32 / damage_per_attack;
+    (GAME_TICK * equipment.attack_speed).mul_f32(attacks) + KILL_OVERHEAD
+}
@@ -1,65 +1,57 @@
 use std::time::Duration;
 
-use crate::{Monster, SlayerMaster, Supplies};
+use strum::IntoEnumIterator;
 
-const GAME_TICK: Duration = Duration::from_millis(600);
+use crate::{
+    Monster, PlayerState, SimulationConfig, SlayerMaster, Supplies, SupplyKind,
+    combat::{self, KillTimeModel, MonsterDefence},
+    drops::{
+        CHASM_CRAWLER_DROPS, DropTable, FLAMING_PYRELORD_DROPS, HILL_GIANT_DROPS, MOSS_GIANT_DROPS,
+    },
+    uncertainty::Uncertainty,
+};
+
+pub const GAME_TICK: Duration = Duration::from_millis(600);
 pub const STORE_TASK_TIME: Duration = Duration::from_secs(3);
 pub const UNSTORE_TASK_TIME: Duration = Duration::from_secs(3);
+pub const STORAGE_UNLOCK_COST: u32 = 500;
+pub const SUPERIOR_UNLOCK_COST: u32 = 150;
+/// Time to bank and get to the gathering spot in each gathering session, TODO: Made up
+pub const RESTOCK_TRIP_TIME: Duration = Duration::from_secs(120);
+/// Time to complete a quest mid-run, unless another is given, TODO: Made up, varies a lot per quest
+pub const QUEST_TIME: Duration = Duration::from_secs(3600);
 
 impl Supplies {
-    pub fn time_to_gather(&self) -> Duration {
-        Duration::from_millis(3033) * self.expeditious_bracelet_charges as u32 // 91 seconds per bracelet
-        + Duration::from_secs(46) * self.bracelet_of_slaughter_charges as u32 // 23 minutes per bracelet
-        + Duration::from_secs(8) * self.games_necklace_charges as u32 // 66 seconds per necklace, TODO: Made up
-        + Duration::from_secs(8) * self.dueling_ring_charges as u32 // 66 seconds per necklace, TODO: Made up
-        + Duration::from_secs(3) * self.necklace_of_passage_charges as u32 // 24 seconds per necklace, assuming spare jades from opal grind
-        + Duration::from_millis(500) * self.chronicle_charges as u32
-        + Duration::from_secs(2) * self.skull_sceptre_charges as u32 // 15 seconds per sceptre, TODO: Made up
-        + Duration::from_secs(8) * self.giantsoul_amulet_charges as u32 // Get big bones from hill giants, and other giants on-task
-        + Duration::from_millis(500) * self.law_runes as u32
+    /// The supplies that are used to travel
+    pub fn teleports() -> [SupplyKind; 7] {
+        [
+            SupplyKind::GamesNecklaceCharges,
+            SupplyKind::DuelingRingCharges,
+            SupplyKind::NecklaceOfPassageCharges,
+            SupplyKind::ChronicleCharges,
+            SupplyKind::SkullSceptreCharges,
+            SupplyKind::GiantsoulAmuletCharges,
+            SupplyKind::LawRunes,
+        ]
+    }
+
+    pub fn get(&self, kind: SupplyKind) -> u64 {
+        match kind {
+            SupplyKind::ExpeditiousBraceletCharges => self.expeditious_bracelet_charges,
+            SupplyKind::BraceletOfSlaughterCharges => self.bracelet_of_slaughter_charges,
+            SupplyKind::GamesNecklaceCharges => self.games_necklace_charges,
+            SupplyKind::DuelingRingCharges => self.dueling_ring_charges,
+            SupplyKind::NecklaceOfPassageCharges => self.necklace_of_passage_charges,
+            SupplyKind::ChronicleCharges => self.chronicle_charges,
+            SupplyKind::SkullSceptreCharges => self.skull_sceptre_charges,
+            SupplyKind::GiantsoulAmuletCharges => self.giantsoul_amulet_charges,
+            SupplyKind::LawRunes => self.law_runes,
+        }
     }
 
-    pub fn print_time_breakdown(&self) {
-        println!("Supplies gathering time:");
-        println!(
-            "Opals for Expeditious Bracelets: {:.1} hours",
-            (Duration::from_millis(3033) * self.expeditious_bracelet_charges as u32).as_secs_f64()
-                / 3600.0
-        );
-        println!(
-            "Red topaz for Bracelets of Slaughter: {:.1} hours",
-            (Duration::from_secs(46) * self.bracelet_of_slaughter_charges as u32).as_secs_f64()
-                / 3600.0
-        );
-        println!(
-            "Games Necklaces: {:.1} hours",
-            (Duration::from_secs(8) * self.games_necklace_charges as u32).as_secs_f64() / 3600.0
-        );
-        println!(
-            "Dueling Rings: {:.1} hours",
-            (Duration::from_secs(8) * self.dueling_ring_charges as u32).as_secs_f64() / 3600.0
-        );
-        println!(
-            "Necklaces of Passage: {:.1} hours",
-            (Duration::from_secs(3) * self.necklace_of_passage_charges as u32).as_secs_f64()
-                / 3600.0
-        );
-        println!(
-            "Chronicle charges: {:.1} hours",
-            (Duration::from_millis(500) * self.chronicle_charges as u32).as_secs_f64() / 3600.0
-        );
-        println!(
-            "Skull Sceptre charges: {:.1} hours",
-            (Duration::from_secs(2) * self.skull_sceptre_charges as u32).as_secs_f64() / 3600.0
-        );
-        println!(
-            "Big Bones for Giantsoul Amulet charges: {:.1} hours",
-            (Duration::from_secs(8) * self.giantsoul_amulet_charges as u32).as_secs_f64() / 3600.0
-        );
-        println!(
-            "Law Runes: {:.1} hours",
-            (Duration::from_millis(500) * self.law_runes as u32).as_secs_f64() / 3600.0
-        );
+    /// The amount of each supply
+    pub fn counts(&self) -> impl Iterator<Item = (SupplyKind, u64)> + '_ {
+        SupplyKind::iter().map(|kind| (kind, self.get(kind)))
     }
 }
 
@@ -67,24 +59,67 @@ impl Supplies {
 pub struct MonsterData {
     pub travel_steps: u32,
     pub time_per_kill: Duration,
+    pub time_per_kill_uncertainty: Option<Uncertainty>, // In seconds
+    pub defence: Option<MonsterDefence>, // Used to derive the kill time from combat stats
     pub travel_supplies: Supplies,
-    pub superior_unique_drop_rate: Option<f32>,
+    pub drop_table: Option<&'static DropTable>,
+    pub superior: Option<SuperiorData>,
     pub use_expeditious_bracelet: bool,
     pub use_bracelet_of_slaughter: bool,
 }
 
+pub struct SuperiorData {
+    pub hitpoints: u32,
+    pub slayer_exp: u32,
+    pub time_per_kill: Duration,
+    pub defence: Option<MonsterDefence>, // Used to derive the kill time from combat stats
+    pub drop_table: &'static DropTable,
+}
+
+/// Time to kill a single monster or superior. Uses the hand-measured `time_per_kill` if there is no combat data
+fn kill_time(
+    time_per_kill: Duration,
+    defence: Option<&MonsterDefence>,
+    player_state: &PlayerState,
+    config: &SimulationConfig,
+) -> Duration {
+    match (config.kill_time_model, defence) {
+        (KillTimeModel::Combat, Some(defence)) => {
+            combat::kill_time(&player_state.combat_levels, &config.equipment, defence)
+        }
+        _ => time_per_kill,
+    }
+}
+
+impl SuperiorData {
+    pub fn kill_time(&self, player_state: &PlayerState, config: &SimulationConfig) -> Duration {
+        kill_time(
+            self.time_per_kill,
+            self.defence.as_ref(),
+            player_state,
+            config,
+        )
+    }
+}
+
 impl MonsterData {
     pub fn travel_time(&self) -> Duration {
         (GAME_TICK * self.travel_steps).div_f32(1.5) // Assume that we run 50% of the time
     }
+
+    pub fn kill_time(&self, player_state: &PlayerState, config: &SimulationConfig) -> Duration {
+        kill_time(
+            self.time_per_kill,
+            self.defence.as_ref(),
+            player_state,
+            config,
+        )
+    }
 }
 
 impl Monster {
     pub fn has_superior(&self) -> bool {
-        self.task_data()
-            .unwrap()
-            .superior_unique_drop_rate
-            .is_some()
+        self.task_data().unwrap().superior.is_some()
     }
 
     pub fn task_data(&self) -> Option<MonsterData> {
@@ -148,11 +183,19 @@ impl Monster {
             Monster::CaveCrawlers => Some(MonsterData {
                 travel_steps: 190,
                 time_per_kill: Duration::from_millis(7600),
+                time_per_kill_uncertainty: Some(Uncertainty::StdDev(1.0)),
                 travel_supplies: Supplies {
                     law_runes: 1,
                     ..Default::default()
                 },
-                superior_unique_drop_rate: Some(1.0 / 166.2),
+                superior: Some(SuperiorData {
+                    // Chasm Crawler
+                    hitpoints: 60,
+                    slayer_exp: 600,
+                    time_per_kill: Duration::from_secs(45), // TODO: Guesstimated
+                    defence: None,                          // TODO: Defence stats
+                    drop_table: &CHASM_CRAWLER_DROPS,
+                }),
                 use_bracelet_of_slaughter: true,
                 ..Default::default()
             }),
@@ -210,6 +253,11 @@ impl Monster {
             Monster::FireGiants => Some(MonsterData {
                 travel_steps: 0,
                 time_per_kill: Duration::from_millis(480_000),
+                defence: Some(MonsterDefence {
+                    hitpoints: 111,
+                    defence_level: 65,
+                    defence_bonus: 0,
+                }),
                 use_expeditious_bracelet: true,
                 ..Default::default()
             }),
@@ -241,10 +289,16 @@ impl Monster {
             Monster::HillGiants => Some(MonsterData {
                 travel_steps: 5,
                 time_per_kill: Duration::from_millis(7800),
+                defence: Some(MonsterDefence {
+                    hitpoints: 35,
+                    defence_level: 26,
+                    defence_bonus: 0,
+                }),
                 travel_supplies: Supplies {
                     giantsoul_amulet_charges: 1,
                     ..Default::default()
                 },
+                drop_table: Some(&HILL_GIANT_DROPS),
                 use_expeditious_bracelet: false, // Big bones are needed for giantsoul amulet charges
                 ..Default::default()
             }),
@@ -270,6 +324,11 @@ impl Monster {
             Monster::IceGiants => Some(MonsterData {
                 travel_steps: 10,
                 time_per_kill: Duration::from_millis(11_200),
+                defence: Some(MonsterDefence {
+                    hitpoints: 70,
+                    defence_level: 40,
+                    defence_bonus: 0,
+                }),
                 travel_supplies: Supplies {
                     giantsoul_amulet_charges: 1,
                     ..Default::default()
@@ -329,10 +388,16 @@ impl Monster {
             Monster::MossGiants => Some(MonsterData {
                 travel_steps: 22,
                 time_per_kill: Duration::from_millis(12_600),
+                defence: Some(MonsterDefence {
+                    hitpoints: 60,
+                    defence_level: 30,
+                    defence_bonus: 0,
+                }),
                 travel_supplies: Supplies {
                     giantsoul_amulet_charges: 1,
                     ..Default::default()
                 },
+                drop_table: Some(&MOSS_GIANT_DROPS),
                 use_expeditious_bracelet: true,
                 ..Default::default()
             }),
@@ -352,7 +417,14 @@ impl Monster {
             Monster::Pyrefiends => Some(MonsterData {
                 travel_steps: 535,
                 time_per_kill: Duration::from_millis(15000),
-                superior_unique_drop_rate: Some(1.0 / 142.2),
+                superior: Some(SuperiorData {
+                    // Flaming Pyrelord
+                    hitpoints: 125,
+                    slayer_exp: 1250,
+                    time_per_kill: Duration::from_secs(90), // TODO: Guesstimated
+                    defence: None,                          // TODO: Defence stats
+                    drop_table: &FLAMING_PYRELORD_DROPS,
+                }),
                 use_bracelet_of_slaughter: true,
                 ..Default::default()
             }),
@@ -400,6 +472,7 @@ impl Monster {
             Monster::Sourhogs => Some(MonsterData {
                 travel_steps: 72,
                 time_per_kill: Duration::from_millis(8000), // TODO: Guesstimated
+                time_per_kill_uncertainty: Some(Uncertainty::StdDev(2.0)),
                 travel_supplies: Supplies {
                     skull_sceptre_charges: 1,
                     ..Default::default()
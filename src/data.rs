@@ -2,6 +2,9 @@ use std::sync::LazyLock;
 
 use crate::{Assignment, Monster, Quest, WORLD_STATE, WorldState};
 
+/// Chance of a superior spawning on each kill, once "Bigger and Badder" is unlocked
+pub const SUPERIOR_SPAWN_RATE: f32 = 1.0 / 200.0;
+
 static EXP_TABLE: LazyLock<[u32; 100]> = LazyLock::new(exp_table);
 
 fn exp_table() -> [u32; 100] {
@@ -17,6 +20,11 @@ fn exp_table() -> [u32; 100] {
     table
 }
 
+/// The minimum exp needed for `level`
+pub fn exp_for_level(level: u8) -> u32 {
+    EXP_TABLE[level as usize]
+}
+
 pub fn level_for_exp(exp: u32) -> u8 {
     match EXP_TABLE.binary_search(&exp) {
         Ok(index) => index as u8,
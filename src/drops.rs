@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use strum::{Display, EnumIter, EnumString};
+
+use crate::SupplyKind;
+
+#[derive(
+    EnumIter, EnumString, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Item {
+    BigBones,
+    DustBattlestaff,
+    EternalGem,
+    GiantKey,
+    ImbuedHeart,
+    LawRunes,
+    MistBattlestaff,
+    MossyKey,
+    UncutEmerald,
+    UncutJade,
+    UncutOpal,
+    UncutRedTopaz,
+}
+
+impl Item {
+    /// The supply that the item can stand in for, and the units of it per item.
+    /// Gems still need cutting, crafting and enchanting before they are jewellery,
+    /// so they are credited against the sources that start from them instead, see `SupplySource::gem`
+    pub fn supply(self) -> Option<(SupplyKind, u64)> {
+        match self {
+            Item::BigBones => Some((SupplyKind::GiantsoulAmuletCharges, 1)),
+            Item::LawRunes => Some((SupplyKind::LawRunes, 1)),
+            _ => None,
+        }
+    }
+}
+
+pub enum Drop {
+    Item(Item, u32), // (item, quantity)
+    Table(&'static DropTable),
+}
+
+pub struct DropTable {
+    /// Exactly one entry is rolled, with a `weight / total_weight` chance. Any weight not covered by an entry drops nothing
+    pub entries: &'static [(f32, Drop)],
+    pub total_weight: f32,
+    /// Each tertiary drop is rolled independently of the main roll, with the given chance.
+    /// Guaranteed drops are tertiary drops with a chance of 1
+    pub tertiary: &'static [(f32, Drop)],
+}
+
+impl Drop {
+    fn roll<R: Rng>(&self, rng: &mut R, drops: &mut BTreeMap<Item, u64>) {
+        match self {
+            Drop::Item(item, quantity) => *drops.entry(*item).or_default() += *quantity as u64,
+            Drop::Table(table) => table.roll(rng, drops),
+        }
+    }
+}
+
+impl DropTable {
+    /// Roll the table once, adding any drops to `drops`
+    pub fn roll<R: Rng>(&self, rng: &mut R, drops: &mut BTreeMap<Item, u64>) {
+        let mut roll = rng.random::<f32>() * self.total_weight;
+        for (weight, drop) in self.entries {
+            if roll < *weight {
+                drop.roll(rng, drops);
+                break;
+            }
+            roll -= weight;
+        }
+        for (chance, drop) in self.tertiary {
+            if rng.random::<f32>() < *chance {
+                drop.roll(rng, drops);
+            }
+        }
+    }
+}
+
+/// Superiors roll one of two unique tables, each with the superior's unique drop rate.
+/// The first table always gives a battlestaff or an imbued heart
+pub static SUPERIOR_UNIQUE_TABLE: DropTable = DropTable {
+    entries: &[
+        (7.0, Drop::Item(Item::DustBattlestaff, 1)),
+        (7.0, Drop::Item(Item::MistBattlestaff, 1)),
+        (2.0, Drop::Item(Item::ImbuedHeart, 1)),
+    ],
+    total_weight: 16.0,
+    tertiary: &[],
+};
+
+/// The second superior unique table only gives an eternal gem 1/8 of the time
+pub static SUPERIOR_GEM_TABLE: DropTable = DropTable {
+    entries: &[(1.0, Drop::Item(Item::EternalGem, 1))],
+    total_weight: 8.0,
+    tertiary: &[],
+};
+
+pub static CHASM_CRAWLER_DROPS: DropTable = DropTable {
+    entries: &[
+        (1.0 / 166.2, Drop::Table(&SUPERIOR_UNIQUE_TABLE)),
+        (1.0 / 166.2, Drop::Table(&SUPERIOR_GEM_TABLE)),
+    ],
+    total_weight: 1.0,
+    tertiary: &[],
+};
+
+pub static FLAMING_PYRELORD_DROPS: DropTable = DropTable {
+    entries: &[
+        (1.0 / 142.2, Drop::Table(&SUPERIOR_UNIQUE_TABLE)),
+        (1.0 / 142.2, Drop::Table(&SUPERIOR_GEM_TABLE)),
+    ],
+    total_weight: 1.0,
+    tertiary: &[],
+};
+
+/// The gem drop table, with only the gems that jewellery is crafted from
+pub static GEM_DROP_TABLE: DropTable = DropTable {
+    entries: &[(16.0, Drop::Item(Item::UncutEmerald, 1))],
+    total_weight: 128.0,
+    tertiary: &[],
+};
+
+pub static HILL_GIANT_DROPS: DropTable = DropTable {
+    entries: &[
+        (2.0, Drop::Item(Item::LawRunes, 2)),
+        (3.0, Drop::Table(&GEM_DROP_TABLE)), // TODO: Check the rate
+    ],
+    total_weight: 128.0,
+    tertiary: &[
+        (1.0, Drop::Item(Item::BigBones, 1)),
+        (1.0 / 128.0, Drop::Item(Item::GiantKey, 1)),
+    ],
+};
+
+pub static MOSS_GIANT_DROPS: DropTable = DropTable {
+    entries: &[
+        (4.0, Drop::Item(Item::LawRunes, 3)),
+        (4.0, Drop::Table(&GEM_DROP_TABLE)), // TODO: Check the rate
+    ],
+    total_weight: 128.0,
+    tertiary: &[
+        (1.0, Drop::Item(Item::BigBones, 1)),
+        (1.0 / 150.0, Drop::Item(Item::MossyKey, 1)),
+    ],
+};
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use rand::Rng;
+use rand_distr::{Distribution, Gamma, LogNormal};
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DurationDistribution {
+    /// Every kill and trip takes exactly the estimated time
+    #[default]
+    Fixed,
+    /// Gamma distributed, with the estimated time as the mean
+    Gamma,
+    /// Log-normally distributed, with the estimated time as the mean
+    LogNormal,
+}
+
+/// How long each single kill or trip takes, with the estimated time as the mean
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DurationModel {
+    pub distribution: DurationDistribution,
+    pub coefficient_of_variation: f32, // Standard deviation divided by the mean
+}
+
+impl DurationModel {
+    /// The time taken by a single kill or trip, with a mean of `mean`
+    pub fn sample<R: Rng>(&self, mean: Duration, rng: &mut R) -> Duration {
+        self.sample_sum(mean, 1, rng)
+    }
+
+    /// The total time taken by `count` independent kills or trips, each with a mean of `mean`
+    pub fn sample_sum<R: Rng>(&self, mean: Duration, count: u64, rng: &mut R) -> Duration {
+        let cv = self.coefficient_of_variation;
+        if count == 0 || mean.is_zero() || cv <= 0.0 {
+            return mean * count as u32;
+        }
+        let mean_secs = mean.as_secs_f32();
+        match self.distribution {
+            DurationDistribution::Fixed => mean * count as u32,
+            DurationDistribution::Gamma => {
+                // A sum of gamma distributed values with the same scale is also gamma distributed
+                let shape = count as f32 / (cv * cv);
+                let scale = mean_secs * cv * cv;
+                let gamma = Gamma::new(shape, scale).unwrap_or_else(|err| {
+                    panic!("Invalid gamma distribution for {:?}: {}", mean, err)
+                });
+                Duration::from_secs_f32(gamma.sample(rng))
+            }
+            DurationDistribution::LogNormal => {
+                let sigma = (1.0 + cv * cv).ln().sqrt();
+                let mu = mean_secs.ln() - sigma * sigma / 2.0;
+                let log_normal = LogNormal::new(mu, sigma).unwrap_or_else(|err| {
+                    panic!("Invalid log-normal distribution for {:?}: {}", mean, err)
+                });
+                Duration::from_secs_f32((0..count).map(|_| log_normal.sample(rng)).sum())
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use crate::{Monster, SlayerMaster, SlayerState, TaskState};
+
+/// Width of the task count buckets when printing how the lock risk changes over time
+const TASK_BUCKET_SIZE: u64 = 100;
+
+/// The state a simulation ended in when it got slayer-locked
+#[derive(Clone, Debug)]
+pub struct SlayerLock {
+    pub monster: Monster,
+    pub master: SlayerMaster,
+    pub points: u32,
+    pub task_streak: u32,
+    pub stored_task: Option<Monster>,
+    pub tasks_received: u64,
+}
+
+impl SlayerLock {
+    /// Returns `None` if there is no active task to blame for the lock
+    pub fn new(slayer_state: &SlayerState) -> Option<Self> {
+        let TaskState::Active((monster, master, _)) = slayer_state.task_state else {
+            return None;
+        };
+        Some(Self {
+            monster,
+            master,
+            points: slayer_state.points,
+            task_streak: slayer_state.task_streak,
+            stored_task: slayer_state.stored_task.map(|(monster, _, _)| monster),
+            tasks_received: slayer_state
+                .slayer_data
+                .total_tasks_received
+                .values()
+                .sum::<u64>(),
+        })
+    }
+}
+
+/// Print which tasks caused the slayer locks, and the chance of getting locked
+/// at different points of the simulation. `tasks_per_run` includes both successful and failed runs
+pub fn print_failure_report(locks: &[SlayerLock], tasks_per_run: &[u64]) {
+    let mut locks_per_task: BTreeMap<(SlayerMaster, Monster), Vec<&SlayerLock>> = BTreeMap::new();
+    for lock in locks {
+        locks_per_task
+            .entry((lock.master, lock.monster))
+            .or_default()
+            .push(lock);
+    }
+    let mut locks_per_task: Vec<_> = locks_per_task.into_iter().collect();
+    locks_per_task.sort_by_key(|(_, locks)| std::cmp::Reverse(locks.len()));
+
+    println!("Slayer locks per task:");
+    for ((master, monster), task_locks) in locks_per_task {
+        let mut points: Vec<u32> = task_locks.iter().map(|lock| lock.points).collect();
+        points.sort();
+        let mut streaks: Vec<u32> = task_locks.iter().map(|lock| lock.task_streak).collect();
+        streaks.sort();
+        let num_stored = task_locks
+            .iter()
+            .filter(|lock| lock.stored_task.is_some())
+            .count();
+        println!(
+            "{:10} {:17} {:6} locks ({:5.1}%), median {} points and {} streak, {:.1}% with a stored task",
+            master,
+            monster,
+            task_locks.len(),
+            100.0 * task_locks.len() as f32 / locks.len() as f32,
+            points[points.len() / 2],
+            streaks[streaks.len() / 2],
+            100.0 * num_stored as f32 / task_locks.len() as f32
+        );
+    }
+
+    println!();
+    println!("Slayer lock risk per {} tasks received:", TASK_BUCKET_SIZE);
+    let max_tasks = tasks_per_run.iter().max().copied().unwrap_or(0);
+    for bucket_start in (0..=max_tasks).step_by(TASK_BUCKET_SIZE as usize) {
+        let bucket_end = bucket_start + TASK_BUCKET_SIZE;
+        let runs_at_risk = tasks_per_run
+            .iter()
+            .filter(|tasks| **tasks >= bucket_start)
+            .count();
+        let bucket_locks = locks
+            .iter()
+            .filter(|lock| (bucket_start..bucket_end).contains(&lock.tasks_received))
+            .count();
+        println!(
+            "{:5}-{:5} tasks: {:6} runs, {:5} locks, {:.2}% locked",
+            bucket_start,
+            bucket_end,
+            runs_at_risk,
+            bucket_locks,
+            100.0 * bucket_locks as f32 / runs_at_risk as f32
+        );
+    }
+}
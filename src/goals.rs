@@ -0,0 +1,164 @@
+use std::{fmt, str::FromStr};
+
+use crate::{PlayerState, SimulationAction, SlayerState, Strategy, drops::Item};
+
+/// A condition for successfully ending a simulation.
+/// Can be parsed from strings like `level=85`, `item=ImbuedHeart:2` or `points=500 and hours=100 or tasks=1000`,
+/// where `and` binds tighter than `or`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Goal {
+    SlayerLevel(u8),
+    Points(u32),
+    Item(Item, u64), // (item, amount)
+    Hours(f32),
+    Tasks(u64), // Tasks completed, not counting skipped tasks
+    All(Vec<Goal>),
+    Any(Vec<Goal>),
+}
+
+impl Goal {
+    pub fn is_reached(&self, slayer_state: &SlayerState, player_state: &PlayerState) -> bool {
+        let slayer_data = &slayer_state.slayer_data;
+        match self {
+            Goal::SlayerLevel(level) => player_state.slayer_level() >= *level,
+            Goal::Points(points) => slayer_state.points >= *points,
+            Goal::Item(item, amount) => {
+                slayer_data.drops.get(item).copied().unwrap_or(0) >= *amount
+            }
+            Goal::Hours(hours) => slayer_data.time_spent().as_secs_f32() / 3600.0 >= *hours,
+            Goal::Tasks(tasks) => slayer_data.total_tasks_done.values().sum::<u64>() >= *tasks,
+            Goal::All(goals) => goals
+                .iter()
+                .all(|goal| goal.is_reached(slayer_state, player_state)),
+            Goal::Any(goals) => goals
+                .iter()
+                .any(|goal| goal.is_reached(slayer_state, player_state)),
+        }
+    }
+
+    fn parse_single(s: &str) -> Result<Self, String> {
+        let (kind, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected a goal on the form kind=value, got '{}'", s))?;
+        let parse_error =
+            |err: &dyn fmt::Display| format!("Invalid value in goal '{}': {}", s, err);
+        match kind.trim() {
+            "level" => Ok(Goal::SlayerLevel(
+                value.trim().parse().map_err(|err| parse_error(&err))?,
+            )),
+            "points" => Ok(Goal::Points(
+                value.trim().parse().map_err(|err| parse_error(&err))?,
+            )),
+            "item" => {
+                let (item, amount) = value.split_once(':').unwrap_or((value, "1"));
+                Ok(Goal::Item(
+                    item.trim().parse().map_err(|err| parse_error(&err))?,
+                    amount.trim().parse().map_err(|err| parse_error(&err))?,
+                ))
+            }
+            "hours" => Ok(Goal::Hours(
+                value.trim().parse().map_err(|err| parse_error(&err))?,
+            )),
+            "tasks" => Ok(Goal::Tasks(
+                value.trim().parse().map_err(|err| parse_error(&err))?,
+            )),
+            _ => Err(format!(
+                "Unknown goal '{}', expected one of level, points, item, hours or tasks",
+                kind
+            )),
+        }
+    }
+}
+
+impl FromStr for Goal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut any_goals = s
+            .split(" or ")
+            .map(|any_goal| {
+                let mut all_goals = any_goal
+                    .split(" and ")
+                    .map(Goal::parse_single)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(if all_goals.len() == 1 {
+                    all_goals.pop().unwrap()
+                } else {
+                    Goal::All(all_goals)
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(if any_goals.len() == 1 {
+            any_goals.pop().unwrap()
+        } else {
+            Goal::Any(any_goals)
+        })
+    }
+}
+
+impl fmt::Display for Goal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Goal::SlayerLevel(level) => write!(f, "level={}", level),
+            Goal::Points(points) => write!(f, "points={}", points),
+            Goal::Item(item, amount) => write!(f, "item={}:{}", item, amount),
+            Goal::Hours(hours) => write!(f, "hours={}", hours),
+            Goal::Tasks(tasks) => write!(f, "tasks={}", tasks),
+            Goal::All(goals) => write_joined(f, goals, " and "),
+            Goal::Any(goals) => write_joined(f, goals, " or "),
+        }
+    }
+}
+
+fn write_joined(f: &mut fmt::Formatter<'_>, goals: &[Goal], separator: &str) -> fmt::Result {
+    for (i, goal) in goals.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}", goal)?;
+    }
+    Ok(())
+}
+
+/// Runs `strategy` until `goal` is reached, or the strategy gets slayer-locked.
+/// Any other termination condition in the strategy is ignored
+#[derive(Clone)]
+pub struct GoalStrategy<S> {
+    pub strategy: S,
+    pub goal: Goal,
+}
+
+impl<S: Strategy> GoalStrategy<S> {
+    /// Wrap `strategy`, falling back to its default goal if none is given
+    pub fn new(strategy: S, goal: Option<Goal>) -> Self {
+        let goal = goal.unwrap_or_else(|| strategy.default_goal());
+        Self { strategy, goal }
+    }
+}
+
+impl<S: Strategy> Strategy for GoalStrategy<S> {
+    fn default_goal(&self) -> Goal {
+        self.goal.clone()
+    }
+
+    fn should_terminate(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> Option<bool> {
+        if self.goal.is_reached(slayer_state, player_state) {
+            return Some(true);
+        }
+        self.strategy
+            .should_terminate(slayer_state, player_state)
+            .filter(|success| !success)
+    }
+
+    fn select_action(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> SimulationAction {
+        self.strategy.select_action(slayer_state, player_state)
+    }
+}
@@ -0,0 +1,314 @@
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    PlayerState, SimulationConfig, Supplies, SupplyKind,
+    costs::RESTOCK_TRIP_TIME,
+    drops::Item,
+    jewellery::{EQUIP_TIME, Jewellery, WornJewellery},
+    sensitivity::Parameter,
+    sources,
+};
+
+/// Units of each supply, where a unit of jewellery is a whole item with all its charges, unlike in `Supplies`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stock(BTreeMap<SupplyKind, u64>);
+
+impl Stock {
+    pub fn get(&self, supply: SupplyKind) -> u64 {
+        self.0.get(&supply).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, supply: SupplyKind, units: u64) {
+        *self.0.entry(supply).or_default() += units;
+    }
+
+    pub fn remove(&mut self, supply: SupplyKind, units: u64) {
+        let stock = self.0.entry(supply).or_default();
+        assert!(
+            *stock >= units,
+            "Cannot take {} units of {} from a stock of {}",
+            units,
+            supply,
+            stock
+        );
+        *stock -= units;
+    }
+
+    /// Raise the units of `supply` to `units`, if it is lower
+    fn raise_to(&mut self, supply: SupplyKind, units: u64) {
+        let stock = self.0.entry(supply).or_default();
+        *stock = (*stock).max(units);
+    }
+
+    /// The supplies with any units, in `SupplyKind` order
+    pub fn iter(&self) -> impl Iterator<Item = (SupplyKind, u64)> + '_ {
+        self.0
+            .iter()
+            .filter(|(_, units)| **units > 0)
+            .map(|(supply, units)| (*supply, *units))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Units needed to hold `amount` of `supply`, counted like in `Supplies`.
+    /// Jewellery charges are rounded up to whole items
+    pub fn units(supply: SupplyKind, amount: u64) -> u64 {
+        match Jewellery::from_supply(supply) {
+            Some(jewellery) => amount.div_ceil(jewellery.charges()),
+            None => amount,
+        }
+    }
+}
+
+/// Parses a list like `law_runes=200, games_necklace_charges=40`. Amounts are counted like in `Supplies`,
+/// in charges or runes, so jewellery charges are rounded up to whole items
+impl FromStr for Stock {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stock = Stock::default();
+        for entry in s.split(',') {
+            let (supply, amount) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Expected supply=amount, got '{}'", entry.trim()))?;
+            let supply: SupplyKind = supply
+                .trim()
+                .parse()
+                .map_err(|_| format!("Unknown supply '{}'", supply.trim()))?;
+            let amount: u64 = amount
+                .trim()
+                .parse()
+                .map_err(|err| format!("Invalid amount of {} '{}': {}", supply, amount, err))?;
+            if amount == 0 {
+                return Err(format!("Amount of {} must be positive", supply));
+            }
+            stock.add(supply, Stock::units(supply, amount));
+        }
+        Ok(stock)
+    }
+}
+
+/// A single gathering session, started when a supply ran out
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restock {
+    pub supply: SupplyKind,
+    pub amount: u64,
+    pub started_at: Duration, // Time spent in the run when the session started
+    pub time: Duration,
+}
+
+/// Supplies on hand during a run, counted in units, so spare jewellery is counted in items.
+/// Supplies without a restock amount are gathered exactly when they are used, so their stock only holds byproducts.
+/// Byproducts are supplies dropped on task, which are used before anything is gathered
+#[derive(Clone, Debug, Default)]
+pub struct Inventory {
+    pub stock: Stock,
+    pub restock_amounts: Stock, // Units of each supply gathered in each session
+    pub peak_stock: Stock,      // Highest stock of each supply during the run
+    pub restocks: Vec<Restock>,
+    pub worn: WornJewellery,
+    pub routes: BTreeMap<(SupplyKind, &'static str), (u64, Duration)>, // Units and time gathered, by supply and source
+    pub byproducts: Stock,         // Units credited from drops during the run
+    pub gems: BTreeMap<Item, u64>, // Uncut gems dropped during the run and not used yet
+    pub gems_used: BTreeMap<Item, u64>, // Dropped gems that were turned into jewellery instead of gathered
+}
+
+impl Inventory {
+    /// Start with an empty stock, and gather the units in `restock_amounts` of a supply whenever it runs out
+    pub fn with_restocks(restock_amounts: Stock) -> Self {
+        Self {
+            restock_amounts,
+            ..Default::default()
+        }
+    }
+
+    /// Start with `stock` on hand instead of an empty stock
+    pub fn with_stock(self, stock: Stock) -> Self {
+        Self {
+            peak_stock: stock.clone(),
+            stock,
+            ..self
+        }
+    }
+
+    /// Add the supplies among `drops` to the stock, and keep the gems that jewellery is crafted from
+    pub fn credit(&mut self, drops: &BTreeMap<Item, u64>) {
+        for (item, amount) in drops {
+            if sources::is_jewellery_gem(*item) {
+                *self.gems.entry(*item).or_default() += amount;
+            }
+            let Some((supply, units)) = item.supply() else {
+                continue;
+            };
+            self.stock.add(supply, amount * units);
+            self.peak_stock.raise_to(supply, self.stock.get(supply));
+            self.byproducts.add(supply, amount * units);
+        }
+    }
+
+    /// Use `supplies`, taking new jewellery and everything else from the stock, and restocking anything that runs out first.
+    /// Each supply is gathered from its cheapest available source.
+    /// Returns the time spent gathering and equipping, with `elapsed` as the time spent in the run so far
+    pub fn take(
+        &mut self,
+        supplies: &Supplies,
+        player_state: &PlayerState,
+        config: &SimulationConfig,
+        elapsed: Duration,
+    ) -> Duration {
+        // Charges are used from the worn jewellery, so only crumbled items have to be replaced
+        let mut new_items = 0;
+        let units: Vec<(SupplyKind, u64)> = supplies
+            .counts()
+            .map(|(name, amount)| match Jewellery::from_supply(name) {
+                Some(jewellery) => {
+                    let items = self.worn.use_charges(jewellery, amount);
+                    new_items += items;
+                    (name, items)
+                }
+                None => (name, amount),
+            })
+            .collect();
+        let equip_time = EQUIP_TIME * new_items as u32;
+
+        let mut gathering_time = Duration::ZERO;
+        for (name, needed) in units {
+            let stock = self.stock.get(name);
+            let restock_amount = self.restock_amounts.get(name);
+            if restock_amount == 0 {
+                let from_stock = needed.min(stock);
+                self.stock.remove(name, from_stock);
+                if needed > from_stock {
+                    gathering_time += self.gather(name, needed - from_stock, player_state, config);
+                }
+                continue;
+            }
+            if stock < needed {
+                // Gather whole sessions' worth until there is enough
+                let sessions = (needed - stock).div_ceil(restock_amount);
+                let amount = sessions * restock_amount;
+                let time = RESTOCK_TRIP_TIME + self.gather(name, amount, player_state, config);
+                self.restocks.push(Restock {
+                    supply: name,
+                    amount,
+                    started_at: elapsed + gathering_time,
+                    time,
+                });
+                gathering_time += time;
+                self.stock.add(name, amount);
+                self.peak_stock.raise_to(name, stock + amount);
+            }
+            self.stock.remove(name, needed);
+        }
+        gathering_time + equip_time
+    }
+
+    /// Gather `amount` units of `supply` from its cheapest available source, and record the route.
+    /// Units are crafted from dropped gems first, if the source uses them
+    fn gather(
+        &mut self,
+        supply: SupplyKind,
+        amount: u64,
+        player_state: &PlayerState,
+        config: &SimulationConfig,
+    ) -> Duration {
+        let source = sources::cheapest_source(supply, player_state);
+        let from_gems = match source.gem {
+            Some(gem) => {
+                let gems = self.gems.entry(gem).or_default();
+                let used = amount.min(*gems);
+                *gems -= used;
+                if used > 0 {
+                    *self.gems_used.entry(gem).or_default() += used;
+                }
+                used
+            }
+            None => 0,
+        };
+        let time = config.cost_factors.scale(
+            Parameter::SupplyGathering(supply, source.name),
+            source.time_per_unit * (amount - from_gems) as u32
+                + sources::GEM_PROCESSING_TIME * from_gems as u32,
+        );
+        let route = self.routes.entry((supply, source.name)).or_default();
+        route.0 += amount;
+        route.1 += time;
+        time
+    }
+
+    /// Total time spent gathering, including the trip to each gathering session, without equipping jewellery
+    pub fn gathering_time(&self) -> Duration {
+        self.routes
+            .values()
+            .map(|(_, time)| *time)
+            .sum::<Duration>()
+            + RESTOCK_TRIP_TIME * self.restocks.len() as u32
+    }
+
+    /// Print the time spent on each gathering route that was used, and the byproducts credited from drops
+    pub fn print_time_breakdown(&self) {
+        println!("Supplies gathering time:");
+        for ((supply, source), (units, time)) in &self.routes {
+            println!(
+                "{} ({}): {:.1} hours, {} units",
+                supply,
+                source,
+                time.as_secs_f64() / 3600.0,
+                units
+            );
+        }
+        if !self.restocks.is_empty() {
+            println!(
+                "Trips to gathering sessions: {:.1} hours, {} sessions",
+                (RESTOCK_TRIP_TIME * self.restocks.len() as u32).as_secs_f64() / 3600.0,
+                self.restocks.len()
+            );
+        }
+        for (supply, units) in self.byproducts.iter() {
+            println!("{} (dropped on task): {} units", supply, units);
+        }
+        for (gem, used) in &self.gems_used {
+            println!("{} (dropped on task): {} crafted into jewellery", gem, used);
+        }
+    }
+
+    /// Print the number of gathering sessions of each supply, when they happened, and the peak stock
+    pub fn print_restocks(&self) {
+        if self.restock_amounts.is_empty() {
+            return;
+        }
+        println!(
+            "{:30} {:>8} {:>9} {:>9} {:>8} {:>6}",
+            "Gathering sessions", "sessions", "first", "interval", "hours", "peak"
+        );
+        for name in SupplyKind::iter() {
+            let restocks: Vec<&Restock> = self
+                .restocks
+                .iter()
+                .filter(|restock| restock.supply == name)
+                .collect();
+            let (Some(first), Some(last)) = (restocks.first(), restocks.last()) else {
+                continue;
+            };
+            let hours = |duration: Duration| duration.as_secs_f32() / 3600.0;
+            let interval = if restocks.len() > 1 {
+                hours(last.started_at - first.started_at) / (restocks.len() - 1) as f32
+            } else {
+                0.0
+            };
+            println!(
+                "{:30} {:8} {:8.1}h {:8.1}h {:7.1}h {:6}",
+                name,
+                restocks.len(),
+                hours(first.started_at),
+                interval,
+                hours(restocks.iter().map(|restock| restock.time).sum()),
+                self.peak_stock.get(name)
+            );
+        }
+    }
+}
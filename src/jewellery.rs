@@ -0,0 +1,70 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::SupplyKind;
+
+/// Time to take a new piece of jewellery out of the inventory and equip it, TODO: Made up
+pub const EQUIP_TIME: Duration = Duration::from_millis(1800);
+
+/// Jewellery that is used up charge by charge, and crumbles when the last charge is used
+#[derive(EnumIter, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Jewellery {
+    ExpeditiousBracelet,
+    BraceletOfSlaughter,
+    GamesNecklace,
+    RingOfDueling,
+    NecklaceOfPassage,
+}
+
+impl Jewellery {
+    pub fn charges(self) -> u64 {
+        match self {
+            Jewellery::ExpeditiousBracelet => 30,
+            Jewellery::BraceletOfSlaughter => 30,
+            Jewellery::GamesNecklace => 8,
+            Jewellery::RingOfDueling => 8,
+            Jewellery::NecklaceOfPassage => 5,
+        }
+    }
+
+    /// The supply that counts the charges
+    pub fn supply(self) -> SupplyKind {
+        match self {
+            Jewellery::ExpeditiousBracelet => SupplyKind::ExpeditiousBraceletCharges,
+            Jewellery::BraceletOfSlaughter => SupplyKind::BraceletOfSlaughterCharges,
+            Jewellery::GamesNecklace => SupplyKind::GamesNecklaceCharges,
+            Jewellery::RingOfDueling => SupplyKind::DuelingRingCharges,
+            Jewellery::NecklaceOfPassage => SupplyKind::NecklaceOfPassageCharges,
+        }
+    }
+
+    pub fn from_supply(supply: SupplyKind) -> Option<Self> {
+        Self::iter().find(|jewellery| jewellery.supply() == supply)
+    }
+}
+
+/// The charges left on each worn piece of jewellery
+#[derive(Clone, Debug, Default)]
+pub struct WornJewellery {
+    charges_left: BTreeMap<Jewellery, u64>,
+    pub items_equipped: BTreeMap<Jewellery, u64>,
+}
+
+impl WornJewellery {
+    /// Use `charges` charges of `jewellery`, equipping a new item every time the worn one crumbles.
+    /// Returns the number of new items that were equipped
+    pub fn use_charges(&mut self, jewellery: Jewellery, charges: u64) -> u64 {
+        let charges_left = self.charges_left.entry(jewellery).or_default();
+        let new_items = charges
+            .saturating_sub(*charges_left)
+            .div_ceil(jewellery.charges());
+        *charges_left = *charges_left + new_items * jewellery.charges() - charges;
+        *self.items_equipped.entry(jewellery).or_default() += new_items;
+        new_items
+    }
+
+    pub fn equip_time(&self) -> Duration {
+        EQUIP_TIME * self.items_equipped.values().sum::<u64>() as u32
+    }
+}
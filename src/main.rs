@@ -1,23 +1,57 @@
 use std::{
     collections::BTreeMap,
-    fmt,
+    fmt, fs,
     ops::{self, RangeInclusive},
+    path::PathBuf,
     time::{self, Duration},
 };
 
 use SlayerMaster::*;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 
+mod combat;
 mod costs;
 mod data;
+mod drops;
+mod durations;
+mod failures;
+mod goals;
+mod inventory;
+mod jewellery;
+mod metrics;
+mod milestones;
+mod observer;
+mod profile;
+mod quests;
+mod sensitivity;
+mod sources;
+mod survival;
+mod task_table;
 #[cfg(test)]
 mod tests;
+mod trace;
+mod trajectory;
+mod uncertainty;
 
 use rand::{Rng, SeedableRng, rngs::SmallRng};
 use rayon::prelude::*;
-use strum::{Display, EnumIter, IntoEnumIterator};
-
-use crate::costs::{STORE_TASK_TIME, UNSTORE_TASK_TIME};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+use crate::combat::{CombatExp, CombatLevels, CombatSkill, Equipment, KillTimeModel};
+use crate::costs::{STORAGE_UNLOCK_COST, STORE_TASK_TIME, SUPERIOR_UNLOCK_COST, UNSTORE_TASK_TIME};
+use crate::drops::Item;
+use crate::durations::{DurationDistribution, DurationModel};
+use crate::failures::SlayerLock;
+use crate::goals::{Goal, GoalStrategy};
+use crate::inventory::{Inventory, Stock};
+use crate::milestones::MilestoneTracker;
+use crate::observer::{SimulationObserver, SkipKind};
+use crate::profile::{AccountProfile, Unlock};
+use crate::quests::{QuestPlan, QuestStrategy};
+use crate::sensitivity::{CostFactors, Parameter, run_sensitivity};
+use crate::task_table::TaskTableSort;
+use crate::trace::Tracer;
+use crate::trajectory::TrajectorySampler;
 
 #[derive(Display, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -38,57 +72,253 @@ pub struct Args {
     /// Print total kills per monster, per slayer master
     #[arg(long)]
     print_total_kills: bool,
+    /// Print mean and percentile tasks and kills per monster, per slayer master, over all successful runs
+    #[arg(long)]
+    print_task_table: bool,
+    /// Column to sort the task table by
+    #[arg(long, value_enum, default_value_t = TaskTableSort::Hours)]
+    task_table_sort: TaskTableSort,
     /// Print numbers for the probability density function for time taken
     #[arg(long)]
     print_density_function: bool,
+    /// Print the chance of not being slayer-locked yet after a number of tasks or hours
+    #[arg(long)]
+    print_survival_curve: bool,
+    /// Write the survival curves over tasks and hours to this CSV file
+    #[arg(long)]
+    survival_csv: Option<PathBuf>,
+    /// Print when levels, point thresholds, streak milestones and first drops are reached
+    #[arg(long)]
+    print_milestones: bool,
+    /// Print which tasks caused the slayer locks, and how the lock risk depends on the task count
+    #[arg(long)]
+    print_failures: bool,
+    /// Print percentiles of every metric, such as supplies and time spent, computed independently
+    #[arg(long)]
+    print_metric_medians: bool,
+    /// Show the run closest to the median of every metric, instead of the run with the median time
+    #[arg(long)]
+    representative_run: bool,
     #[arg(short, long, default_value_t = 10000)]
     num_simulations: u64,
+    /// Start with the "Bigger and Badder" unlock bought, so superiors can spawn.
+    /// Without it, and without the unlock in the profile, no superiors spawn until a strategy buys it
+    #[arg(long)]
+    superiors_unlocked: bool,
+    /// Superiors spawn with a 1 in N chance per kill
+    #[arg(long, default_value_t = 200, value_parser = clap::value_parser!(u32).range(1..))]
+    superior_spawn_rate: u32,
+    /// Seed for the random numbers of the first simulation, and one more for each following simulation.
+    /// Every simulation gets a random seed if not given
+    #[arg(long)]
+    rng_seed: Option<u64>,
+    /// Draw every estimated cost with a known uncertainty from its distribution, once per simulation
+    #[arg(long)]
+    sample_parameters: bool,
+    /// Standard deviation, in percent of the estimate, for costs without a known uncertainty.
+    /// Only used with --sample-parameters
+    #[arg(long, default_value_t = 0.0, value_parser = uncertainty::parse_default_uncertainty)]
+    default_uncertainty: f32,
+    /// Distribution of the time taken by each single kill and trip, with the estimate as the mean
+    #[arg(long, value_enum, default_value_t = DurationDistribution::Fixed)]
+    duration_distribution: DurationDistribution,
+    /// Standard deviation of each kill and trip time, as a fraction of the mean
+    #[arg(long, default_value_t = 0.3)]
+    duration_cv: f32,
+    /// How the time to kill each monster is decided
+    #[arg(long, value_enum, default_value_t = KillTimeModel::Measured)]
+    kill_time_model: KillTimeModel,
+    /// Limpwurt's attack, strength, defence and hitpoints levels at the start, like 50,60,1,60.
+    /// Without them, slayer masters are not gated on combat level, and the combat kill time model can't be used
+    #[arg(long, required_if_eq("kill_time_model", "combat"))]
+    combat_levels: Option<CombatLevels>,
+    /// Melee skill that gets the combat exp from kills
+    #[arg(long, value_enum, default_value_t = CombatSkill::Strength)]
+    combat_skill: CombatSkill,
+    /// Load the killable monsters, usable slayer masters, teleports and owned unlocks from this file,
+    /// instead of using Limpwurt's
+    #[arg(long)]
+    profile: Option<PathBuf>,
+    /// Complete a quest during each run, like PriestInPeril:1.5 when tasks=100, with the hours it takes
+    /// and the goal to reach first. Can be repeated
+    #[arg(long)]
+    complete_quest: Vec<QuestPlan>,
+    /// Gather supplies in sessions whenever they run out, starting from --start-stock or an empty stock,
+    /// like law_runes=200,games_necklace_charges=40. Amounts are in runes or charges,
+    /// and jewellery is gathered in whole items.
+    /// Supplies that are not listed are gathered exactly when they are used
+    #[arg(long)]
+    restock: Option<Stock>,
+    /// Supplies on hand at the start of each run, written like --restock
+    #[arg(long)]
+    start_stock: Option<Stock>,
+    /// Strategy to simulate
+    #[arg(long, value_enum, default_value_t = StrategyKind::Superiors)]
+    strategy: StrategyKind,
+    /// Stop each simulation once this goal is reached, instead of the strategy's own goal.
+    /// Goals are level=N, points=P, item=Item:K, hours=H or tasks=T, combined with "and" and "or"
+    #[arg(long)]
+    goal: Option<Goal>,
+    /// Write percentile bands of points, exp, level and streak over time to this CSV file
+    #[arg(long)]
+    trajectory_csv: Option<PathBuf>,
+    /// Hours between each sample in the trajectory CSV
+    #[arg(long, default_value_t = 10.0)]
+    trajectory_interval: f32,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Args {
+    fn simulation_config(&self) -> SimulationConfig {
+        assert!(
+            self.duration_cv >= 0.0,
+            "Duration coefficient of variation cannot be negative"
+        );
+        SimulationConfig {
+            rng_seed: self.rng_seed,
+            superior_spawn_rate: 1.0 / self.superior_spawn_rate as f32,
+            cost_factors: CostFactors::default(),
+            duration_model: DurationModel {
+                distribution: self.duration_distribution,
+                coefficient_of_variation: self.duration_cv,
+            },
+            equipment: Equipment::default(), // TODO: Limpwurt's actual gear
+            kill_time_model: self.kill_time_model,
+            combat_skill: self.combat_skill,
+            restock_amounts: self.restock.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Run a single simulation, and print every action taken
+    Trace {
+        /// Write the trace to this file instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Perturb each kill time, travel distance and supply gathering rate,
+    /// and rank them by their effect on the median hours
+    Sensitivity {
+        /// Percentage to perturb each parameter by, in both directions
+        #[arg(long, default_value_t = 10.0)]
+        percent: f32,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyKind {
+    /// Accumulate points, then farm superiors for their unique drops
+    Superiors,
+    /// Get to 1000 points with the smallest risk of getting slayer-locked
+    MinimizeSlayerLock,
+    /// Like minimize-slayer-lock, but unlocks task storage and compares against not doing so
+    TaskStorage,
 }
 
 fn main() {
     let args = Args::parse();
 
-    let _start = match WORLD_STATE {
-        WorldState::Limp2024 => SimulationStartPoint {
-            slayer_exp: 168_538,
-            quests_done: vec![Quest::PorcineOfInterest],
-            task_streak: 0,
-            points: 0,
-            task_state: TaskState::Active((Monster::Hellhounds, Vannaka, 40)),
-            storage_unlocked: false,
-        },
-        WorldState::Limp2025 => SimulationStartPoint {
-            slayer_exp: 1_308_538,
-            quests_done: vec![Quest::LostCity, Quest::PorcineOfInterest],
-            task_streak: 1,
-            points: 120,
-            task_state: TaskState::Active((Monster::Monkeys, Turael, 20)),
-            storage_unlocked: false,
-        },
-        WorldState::Limp2026 => SimulationStartPoint {
-            slayer_exp: 1_308_538,
-            quests_done: vec![
-                Quest::LostCity,
-                Quest::PorcineOfInterest,
-                Quest::DragonSlayer,
-            ],
-            task_streak: 1,
-            points: 120,
-            task_state: TaskState::Active((Monster::Monkeys, Turael, 20)),
-            storage_unlocked: false,
-        },
-    };
-    // run_slayer_start_simulation();
-    run_superiors_simulation(args);
+    if let Some(Command::Trace { output }) = args.command.clone() {
+        let start = slayer_update_start_point(&args);
+        let config = args.simulation_config();
+        match args.strategy {
+            StrategyKind::Superiors => {
+                run_trace(start, &config, SuperiorsStrategy::default(), args, output)
+            }
+            StrategyKind::MinimizeSlayerLock => run_trace(
+                start,
+                &config,
+                MinimizeSlayerLockStrategy::default(),
+                args,
+                output,
+            ),
+            StrategyKind::TaskStorage => {
+                run_trace(start, &config, TaskStorageStrategy::default(), args, output)
+            }
+        }
+        return;
+    }
+    if let Some(Command::Sensitivity { percent }) = args.command {
+        let start = slayer_update_start_point(&args);
+        match args.strategy {
+            StrategyKind::Superiors => {
+                run_sensitivity(start, SuperiorsStrategy::default(), args, percent)
+            }
+            StrategyKind::MinimizeSlayerLock => {
+                run_sensitivity(start, MinimizeSlayerLockStrategy::default(), args, percent)
+            }
+            StrategyKind::TaskStorage => {
+                run_sensitivity(start, TaskStorageStrategy::default(), args, percent)
+            }
+        }
+        return;
+    }
+    match args.strategy {
+        StrategyKind::Superiors => run_superiors_simulation(args),
+        StrategyKind::MinimizeSlayerLock => run_slayer_start_simulation(args),
+        StrategyKind::TaskStorage => run_task_storage_simulation(args),
+    }
 }
 
-fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args: Args) {
+/// Run and report every simulation. Returns the number of tasks received in each slayer-locked run, sorted,
+/// so comparisons can reuse the runs
+fn run_simulation<S: Strategy + Clone + Send + Sync>(
+    start: SimulationStartPoint,
+    strategy: S,
+    args: Args,
+) -> Vec<u64> {
     let start_time = time::Instant::now();
 
-    let results: Vec<_> = (0..args.num_simulations)
+    let strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy,
+            plans: args.complete_quest.clone(),
+        },
+        args.goal.clone(),
+    );
+    println!("Simulating until {}", strategy.goal);
+
+    assert!(
+        args.trajectory_interval > 0.0,
+        "Trajectory interval must be positive"
+    );
+    let trajectory_interval = Duration::from_secs_f32(args.trajectory_interval * 3600.0);
+
+    let config = args.simulation_config();
+    let start_level = data::level_for_exp(start.slayer_exp);
+    let (results, (trajectories, milestones)): (Vec<_>, (Vec<_>, Vec<_>)) = (0..args
+        .num_simulations)
         .into_par_iter()
-        .map(|_| simulate_limpwurt(start.clone(), S::default()))
-        .collect();
+        .map(|i| {
+            let mut sampler = args
+                .trajectory_csv
+                .is_some()
+                .then(|| TrajectorySampler::new(trajectory_interval));
+            let mut milestones = args
+                .print_milestones
+                .then(|| MilestoneTracker::new(start_level));
+            let mut observers: Vec<&mut dyn SimulationObserver> = vec![];
+            if let Some(sampler) = &mut sampler {
+                observers.push(sampler);
+            }
+            if let Some(milestones) = &mut milestones {
+                observers.push(milestones);
+            }
+            let config = run_config(&config, &args, i);
+            let result =
+                simulate_limpwurt(start.clone(), &config, strategy.clone(), &mut observers);
+            (result, (sampler, milestones))
+        })
+        .unzip();
+
+    if let Some(path) = &args.trajectory_csv {
+        let trajectories: Vec<TrajectorySampler> = trajectories.into_iter().flatten().collect();
+        trajectory::write_percentile_bands(path, &trajectories);
+        println!("Wrote trajectories to {}", path.display());
+    }
 
     let mut num_successes = 0;
     let mut num_tasks_received: u64 = 0;
@@ -98,17 +328,22 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
     let mut total_points_per_successful_run = vec![];
     let mut end_points_per_successful_run = vec![];
     let mut slayer_exp_per_failed_run = vec![];
+    let mut slayer_locks = vec![];
+    let mut tasks_survival_runs = vec![];
+    let mut hours_survival_runs = vec![];
 
     let mut all_successful_runs = vec![];
 
     let mut max_points_locked = 0;
-    let mut all_drops = SlayerDrops::default();
+    let mut all_drops: BTreeMap<Item, u64> = BTreeMap::new();
     let mut all_supplies = Supplies::default();
 
     for (slayer_state, player_state, success) in results {
         let slayer_data = slayer_state.slayer_data.clone();
         let num_tasks = slayer_data.total_tasks_received.values().sum::<u64>();
         num_tasks_received += num_tasks;
+        tasks_survival_runs.push((num_tasks as f64, !success));
+        hours_survival_runs.push((slayer_data.time_spent().as_secs_f64() / 3600.0, !success));
         if success {
             num_successes += 1;
             num_tasks_per_successful_run.push(num_tasks);
@@ -121,8 +356,11 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
 
             max_points_locked = max_points_locked.max(slayer_data.max_points);
             num_tasks_per_failed_run.push(num_tasks);
+            slayer_locks.extend(SlayerLock::new(&slayer_state));
+        }
+        for (item, amount) in slayer_data.drops.iter() {
+            *all_drops.entry(*item).or_default() += amount;
         }
-        all_drops = all_drops + slayer_data.drops;
         all_supplies = all_supplies + slayer_data.supplies_used;
     }
 
@@ -143,8 +381,22 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
     let median_min_points = min_points_per_successful_run
         .get(min_points_per_successful_run.len() / 2)
         .unwrap_or(&0);
+    let run_metrics: Vec<Vec<(String, f64)>> =
+        if args.print_metric_medians || args.representative_run {
+            all_successful_runs
+                .iter()
+                .map(|(run, player_data)| metrics::run_metrics(&run.slayer_data, player_data))
+                .collect()
+        } else {
+            vec![]
+        };
+    let median_run_index = if args.representative_run {
+        metrics::most_representative_run(&run_metrics).unwrap_or(0)
+    } else {
+        all_successful_runs.len() / 2
+    };
     let (median_run, median_player_data) = all_successful_runs
-        .get(all_successful_runs.len() / 2)
+        .get(median_run_index)
         .unwrap_or(&Default::default())
         .clone();
     let median_total_points = total_points_per_successful_run
@@ -210,12 +462,27 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
         median_total_points, median_end_points
     );
 
-    println!("Median simulation:");
+    if args.print_metric_medians {
+        println!();
+        metrics::print_metric_medians(&run_metrics);
+        println!();
+    }
+
+    if args.representative_run {
+        println!("Most representative simulation:");
+    } else {
+        println!("Median simulation:");
+    }
     println!(
-        "{} total points, {} total exp, {} end level, {:.1} total hours, {} total tasks",
+        "{} total points, {} total exp, {} end level, {} end combat level, {:.1} total hours, {} total tasks",
         median_run.slayer_data.total_points,
         median_player_data.slayer_exp,
         median_player_data.slayer_level,
+        if median_player_data.combat_levels_known {
+            median_player_data.combat_levels.combat_level().to_string()
+        } else {
+            "unknown".to_string()
+        },
         median_run.slayer_data.time_spent().as_secs_f32() / 3600.0,
         median_run
             .slayer_data
@@ -227,17 +494,49 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
         "New time spent: {:.1} hours",
         median_run.slayer_data.time_spent().as_secs_f32() / 3600.0
     );
+    println!(
+        "{} superiors killed, {} slayer exp from superiors",
+        median_run.slayer_data.superior_kills.values().sum::<u64>(),
+        median_run.slayer_data.superior_slayer_exp()
+    );
+    let mut superior_unlocks: Vec<(u64, Duration)> = all_successful_runs
+        .iter()
+        .filter_map(|(run, _)| run.slayer_data.superiors_unlocked_after)
+        .collect();
+    if !superior_unlocks.is_empty() {
+        superior_unlocks.sort();
+        let median_unlock_tasks = superior_unlocks[superior_unlocks.len() / 2].0;
+        let mut unlock_times: Vec<Duration> =
+            superior_unlocks.iter().map(|(_, time)| *time).collect();
+        unlock_times.sort();
+        println!(
+            "Bought superiors for {} points in {:.1}% of successful runs, median after {} tasks and {:.1} hours",
+            SUPERIOR_UNLOCK_COST,
+            100.0 * superior_unlocks.len() as f32 / all_successful_runs.len() as f32,
+            median_unlock_tasks,
+            unlock_times[unlock_times.len() / 2].as_secs_f32() / 3600.0
+        );
+    }
     println!("Supplies used: {:?}", median_run.slayer_data.supplies_used);
     println!(
         "{:.1} hours spent gathering supplies, {:.1} hours total",
-        median_run
-            .slayer_data
-            .supplies_used
-            .time_to_gather()
-            .as_secs_f32()
-            / 3600.0,
+        median_run.slayer_data.gathering_time().as_secs_f32() / 3600.0,
         median_run.slayer_data.time_spent().as_secs_f32() / 3600.0
     );
+    if args.print_failures && !slayer_locks.is_empty() {
+        println!();
+        let tasks_per_run: Vec<u64> = num_tasks_per_successful_run
+            .iter()
+            .chain(num_tasks_per_failed_run.iter())
+            .copied()
+            .collect();
+        failures::print_failure_report(&slayer_locks, &tasks_per_run);
+    }
+    if args.print_milestones {
+        println!();
+        let milestones: Vec<MilestoneTracker> = milestones.into_iter().flatten().collect();
+        milestones::print_milestone_report(&milestones);
+    }
     if args.print_total_tasks {
         println!();
         println!("Total tasks done per slayer master:");
@@ -264,35 +563,26 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
             }
         }
     }
-    // println!("Average tasks done per slayer master:");
-    // for master in SlayerMaster::iter() {
-    //     for monster in Monster::iter() {
-    //         let kills = all_successful_runs
-    //             .iter()
-    //             .map(|run| {
-    //                 run.0
-    //                     .slayer_data
-    //                     .total_tasks_done
-    //                     .get(&(master, monster))
-    //                     .unwrap_or(&0)
-    //             })
-    //             .sum::<u64>();
-    //         if kills > 0 {
-    //             println!(
-    //                 "{:10} {:17} {}",
-    //                 master,
-    //                 monster,
-    //                 kills / all_successful_runs.len() as u64
-    //             );
-    //         }
-    //     }
-    // }
+    if args.print_task_table {
+        println!();
+        println!("Tasks and kills per slayer master, over all successful runs:");
+        let runs: Vec<&SlayerData> = all_successful_runs
+            .iter()
+            .map(|(run, _)| &run.slayer_data)
+            .collect();
+        task_table::print_task_table(&runs, args.task_table_sort);
+    }
     if args.print_total_kills {
         println!();
         println!("Total kills per slayer master:");
         for ((master, monster), kills) in median_run.slayer_data.total_kills.iter() {
             println!("{:10} {:17} {}", master, monster, kills);
         }
+        println!();
+        println!("Total superior kills per slayer master:");
+        for ((master, monster), kills) in median_run.slayer_data.superior_kills.iter() {
+            println!("{:10} {:17} {}", master, monster, kills);
+        }
     }
 
     println!("Time budget breakdown:");
@@ -317,13 +607,41 @@ fn run_simulation<S: Strategy + Clone + Send>(start: SimulationStartPoint, args:
             println!("[{}, {}],", points * 100, count);
         }
     }
+
+    if args.print_survival_curve || args.survival_csv.is_some() {
+        let task_curve = survival::kaplan_meier(&tasks_survival_runs);
+        let hour_curve = survival::kaplan_meier(&hours_survival_runs);
+        if args.print_survival_curve {
+            survival::print_survival_table("tasks", &task_curve, 50.0);
+            survival::print_survival_table("hours", &hour_curve, 10.0);
+        }
+        if let Some(path) = &args.survival_csv {
+            survival::write_survival_csv(path, &task_curve, &hour_curve);
+            println!("Wrote survival curves to {}", path.display());
+        }
+    }
+
+    num_tasks_per_failed_run
 }
 
-pub fn run_superiors_simulation(args: Args) {
+/// TODO: Limpwurt's actual combat levels. Only used to track combat exp when the levels are not given,
+/// never to gate slayer masters or derive kill times
+const LIMPWURT_COMBAT_LEVELS: CombatLevels = CombatLevels {
+    attack: 50,
+    strength: 60,
+    defence: 1,
+    hitpoints: 60,
+};
+
+fn slayer_update_start_point(args: &Args) -> SimulationStartPoint {
     // Simulation is only valid after the slayer update
     assert!(WORLD_STATE == WorldState::Limp2026);
 
-    let start = SimulationStartPoint {
+    let profile = match &args.profile {
+        Some(path) => AccountProfile::load(path),
+        None => AccountProfile::limpwurt(),
+    };
+    SimulationStartPoint {
         slayer_exp: 1_308_538,
         quests_done: vec![
             Quest::LostCity,
@@ -333,32 +651,150 @@ pub fn run_superiors_simulation(args: Args) {
         task_streak: 1,
         points: 120,
         task_state: TaskState::Active((Monster::Monkeys, Turael, 20)),
-        storage_unlocked: false,
-    };
+        storage_unlocked: profile.unlocks.contains(&Unlock::TaskStorage),
+        superiors_unlocked: args.superiors_unlocked || profile.unlocks.contains(&Unlock::Superiors),
+        combat_levels: args.combat_levels,
+        profile,
+        stock: args.start_stock.clone().unwrap_or_default(),
+    }
+}
+
+fn run_trace<S: Strategy>(
+    start: SimulationStartPoint,
+    config: &SimulationConfig,
+    strategy: S,
+    args: Args,
+    output: Option<PathBuf>,
+) {
+    let strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy,
+            plans: args.complete_quest,
+        },
+        args.goal,
+    );
+    let mut lines: Vec<String> = vec![format!("Simulating until {}", strategy.goal)];
+
+    let mut tracer = Tracer::default();
+    let (slayer_state, _, success) = simulate_limpwurt(start, config, strategy, &mut [&mut tracer]);
+    let trace = tracer.events;
+
+    lines.extend(trace.iter().map(|event| event.to_string()));
+    lines.push(format!(
+        "{} after {} actions, {:.1} hours",
+        if success {
+            "Reached goal"
+        } else {
+            "Got slayer-locked"
+        },
+        trace.len(),
+        slayer_state.slayer_data.time_spent().as_secs_f32() / 3600.0
+    ));
+
+    match output {
+        Some(path) => {
+            fs::write(&path, lines.join("\n") + "\n").unwrap_or_else(|err| {
+                panic!("Failed to write trace to {}: {}", path.display(), err)
+            });
+            println!("Wrote {} events to {}", trace.len(), path.display());
+        }
+        None => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+pub fn run_superiors_simulation(args: Args) {
+    let start = slayer_update_start_point(&args);
 
-    run_simulation::<SuperiorsStrategy>(start, args);
+    run_simulation(start, SuperiorsStrategy::default(), args);
 }
 
 pub fn run_slayer_start_simulation(args: Args) {
-    // Simulation is only valid after the slayer update
-    assert!(WORLD_STATE == WorldState::Limp2026);
+    let start = slayer_update_start_point(&args);
 
-    let start = SimulationStartPoint {
-        slayer_exp: 1_308_538,
-        quests_done: vec![
-            Quest::LostCity,
-            Quest::PorcineOfInterest,
-            Quest::DragonSlayer,
-        ],
-        task_streak: 1,
-        points: 120,
-        task_state: TaskState::Active((Monster::Monkeys, Turael, 20)),
-        storage_unlocked: false,
+    run_simulation(start, MinimizeSlayerLockStrategy::default(), args);
+}
+
+pub fn run_task_storage_simulation(args: Args) {
+    let start = slayer_update_start_point(&args);
+
+    let config = args.simulation_config();
+    let num_simulations = args.num_simulations;
+    let baseline_strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy: MinimizeSlayerLockStrategy::default(),
+            plans: args.complete_quest.clone(),
+        },
+        args.goal.clone(),
+    );
+    let baseline = locked_run_tasks(&start, &config, &baseline_strategy, &args);
+    let storage = run_simulation(start, TaskStorageStrategy::default(), args);
+
+    println!();
+    println!("Slayer-lock risk compared to not using task storage:");
+    for (name, tasks_at_lock) in [("No task storage", baseline), ("Task storage", storage)] {
+        println!(
+            "{:16} {:6} locked, {:.3}%, {} tasks median at lock",
+            name,
+            tasks_at_lock.len(),
+            100.0 * tasks_at_lock.len() as f32 / num_simulations as f32,
+            tasks_at_lock
+                .get(tasks_at_lock.len() / 2)
+                .copied()
+                .unwrap_or(0)
+        );
+    }
+}
+
+/// The config of run `i`. Its seed is derived from the configured seed, and with --sample-parameters
+/// its cost factors are drawn from that seed too, so seeded runs can be repeated
+fn run_config(config: &SimulationConfig, args: &Args, i: u64) -> SimulationConfig {
+    let mut rng = match config.rng_seed {
+        Some(seed) => SmallRng::seed_from_u64(seed.wrapping_add(i)),
+        None => SmallRng::from_os_rng(),
+    };
+    let cost_factors = if args.sample_parameters {
+        uncertainty::sample_cost_factors(&mut rng, args.default_uncertainty)
+    } else {
+        config.cost_factors.clone()
     };
+    SimulationConfig {
+        rng_seed: Some(rng.random()),
+        cost_factors,
+        ..config.clone()
+    }
+}
 
-    run_simulation::<MinimizeSlayerLockStrategy>(start, args);
+/// Like `run_simulation` with the same run configs, but without observers or reports
+fn locked_run_tasks<S: Strategy + Clone + Send + Sync>(
+    start: &SimulationStartPoint,
+    config: &SimulationConfig,
+    strategy: &S,
+    args: &Args,
+) -> Vec<u64> {
+    let mut tasks_at_lock: Vec<u64> = (0..args.num_simulations)
+        .into_par_iter()
+        .map(|i| {
+            let config = run_config(config, args, i);
+            simulate_limpwurt(start.clone(), &config, strategy.clone(), &mut [])
+        })
+        .filter(|(_, _, success)| !success)
+        .map(|(slayer_state, _, _)| {
+            slayer_state
+                .slayer_data
+                .total_tasks_received
+                .values()
+                .sum::<u64>()
+        })
+        .collect();
+    tasks_at_lock.sort();
+    tasks_at_lock
 }
 
+/// The account's state when a run starts
 #[derive(Clone)]
 struct SimulationStartPoint {
     slayer_exp: u32,
@@ -367,18 +803,56 @@ struct SimulationStartPoint {
     points: u32,
     task_state: TaskState,
     storage_unlocked: bool,
+    superiors_unlocked: bool,
+    combat_levels: Option<CombatLevels>, // None if unknown
+    profile: AccountProfile,
+    stock: Stock, // Supplies on hand, counted in units like the inventory
 }
 
+/// Settings that stay the same for a whole run, and are never changed by it
+#[derive(Clone)]
+struct SimulationConfig {
+    rng_seed: Option<u64>, // Seed for the random number generator, or a random seed if None
+    superior_spawn_rate: f32,
+    cost_factors: CostFactors, // Scaling of estimated costs, for sensitivity and uncertainty analysis
+    duration_model: DurationModel,
+    equipment: Equipment,
+    kill_time_model: KillTimeModel,
+    combat_skill: CombatSkill,
+    restock_amounts: Stock, // Units of each supply gathered per session. Unlisted supplies are gathered when used
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            rng_seed: None,
+            superior_spawn_rate: data::SUPERIOR_SPAWN_RATE,
+            cost_factors: CostFactors::default(),
+            duration_model: DurationModel::default(),
+            equipment: Equipment::default(),
+            kill_time_model: KillTimeModel::default(),
+            combat_skill: CombatSkill::default(),
+            restock_amounts: Stock::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
 enum SimulationAction {
     CompleteTask,
     PointSkip,
     NewAssignment(SlayerMaster),
     UnlockTaskStorage,
+    UnlockSuperiors,
     StoreTask,
     UnstoreTask,
+    CompleteQuest(Quest, Duration),
 }
 
-trait Strategy: Default {
+trait Strategy {
+    /// The goal that ends the simulation successfully, unless another is given on the command line
+    fn default_goal(&self) -> Goal;
+    /// Returns `Some(false)` if the player is slayer-locked, or `Some(true)` on success
     fn should_terminate(
         &mut self,
         slayer_state: &SlayerState,
@@ -391,18 +865,63 @@ trait Strategy: Default {
     ) -> SimulationAction;
 }
 
+/// Whether no slayer master is usable, or the active task can't be killed, Turael-skipped or point-skipped
+fn is_locked_out(slayer_state: &SlayerState, player_state: &PlayerState) -> bool {
+    if player_state.usable_master(&[]).is_none() {
+        return true;
+    }
+    match slayer_state.task_state {
+        TaskState::Active((monster, _, _)) => {
+            !player_state.can_kill(monster)
+                && !Turael.can_assign(monster)
+                && !player_state.can_use_master(Turael)
+                && slayer_state.points < 30
+        }
+        TaskState::Completed(_) | TaskState::None => false,
+    }
+}
+
+/// Skip the active task at Turael if he is usable, or else with points
+fn skip_task(slayer_state: &SlayerState, player_state: &PlayerState) -> SimulationAction {
+    if player_state.can_use_master(Turael) {
+        SimulationAction::NewAssignment(Turael)
+    } else {
+        assert!(
+            slayer_state.points >= 30,
+            "Ran out of slayer points, simulation should have stopped already"
+        );
+        SimulationAction::PointSkip
+    }
+}
+
+/// The next master to get a task from, preferring `preferred` in order
+fn next_master(player_state: &PlayerState, preferred: &[SlayerMaster]) -> SimulationAction {
+    SimulationAction::NewAssignment(
+        player_state
+            .usable_master(preferred)
+            .expect("No usable slayer master, simulation should have stopped already"),
+    )
+}
+
 #[derive(Default, Clone)]
 struct MinimizeSlayerLockStrategy {}
 
 impl Strategy for MinimizeSlayerLockStrategy {
+    fn default_goal(&self) -> Goal {
+        Goal::Points(1000)
+    }
+
     fn should_terminate(
         &mut self,
         slayer_state: &SlayerState,
         player_state: &PlayerState,
     ) -> Option<bool> {
+        if is_locked_out(slayer_state, player_state) {
+            return Some(false);
+        }
         match slayer_state.task_state {
             TaskState::Active((monster, _, _)) => {
-                if !monster.can_limpwurt_kill()
+                if !player_state.can_kill(monster)
                     && slayer_state.points < 30
                     && Turael.can_assign(monster)
                     && (!player_state.storage_unlocked || slayer_state.stored_task.is_some())
@@ -412,21 +931,18 @@ impl Strategy for MinimizeSlayerLockStrategy {
                     None
                 }
             }
-
-            TaskState::Completed(_) if slayer_state.points >= 1000 => Some(true),
-            TaskState::Completed(_) => None,
-            TaskState::None => None,
+            TaskState::Completed(_) | TaskState::None => None,
         }
     }
 
     fn select_action(
         &mut self,
         slayer_state: &SlayerState,
-        _player_state: &PlayerState,
+        player_state: &PlayerState,
     ) -> SimulationAction {
         match slayer_state.task_state {
             TaskState::Active((monster, _, _)) => {
-                if monster.can_limpwurt_kill() {
+                if player_state.can_kill(monster) {
                     SimulationAction::CompleteTask
                 } else if Turael.can_assign(monster) {
                     if slayer_state.points >= 30 {
@@ -435,23 +951,80 @@ impl Strategy for MinimizeSlayerLockStrategy {
                         panic!("Ran out of slayer points, simulation should have stopped already");
                     }
                 } else {
-                    SimulationAction::NewAssignment(Turael)
+                    skip_task(slayer_state, player_state)
                 }
             }
             TaskState::Completed(_) | TaskState::None => {
                 let streak_after_next_task = slayer_state.task_streak + 1;
-                let next_slayer_master =
-                    if streak_after_next_task >= 5 && streak_after_next_task % 10 <= 4 {
-                        Vannaka
-                    } else {
-                        Spria
-                    };
-                SimulationAction::NewAssignment(next_slayer_master)
+                if streak_after_next_task >= 5 && streak_after_next_task % 10 <= 4 {
+                    next_master(player_state, &[Vannaka, Spria])
+                } else {
+                    next_master(player_state, &[Spria])
+                }
             }
         }
     }
 }
 
+/// Points kept in reserve when unlocking task storage
+const TASK_STORAGE_RESERVE_POINTS: u32 = 60;
+
+/// Unlocks task storage once affordable, and uses it to park unkillable Turael tasks
+/// that we can't afford to point-skip. The stored task is unstored and skipped once
+/// we have points to spare, freeing the storage for the next emergency.
+#[derive(Default, Clone)]
+struct TaskStorageStrategy {}
+
+impl Strategy for TaskStorageStrategy {
+    fn default_goal(&self) -> Goal {
+        MinimizeSlayerLockStrategy::default().default_goal()
+    }
+
+    fn should_terminate(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> Option<bool> {
+        MinimizeSlayerLockStrategy::default().should_terminate(slayer_state, player_state)
+    }
+
+    fn select_action(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> SimulationAction {
+        match slayer_state.task_state {
+            TaskState::Active((monster, _, _)) => {
+                if !player_state.can_kill(monster)
+                    && Turael.can_assign(monster)
+                    && slayer_state.points < 30
+                {
+                    assert!(
+                        player_state.storage_unlocked && slayer_state.stored_task.is_none(),
+                        "Cannot store task, simulation should have stopped already"
+                    );
+                    return SimulationAction::StoreTask;
+                }
+            }
+            TaskState::Completed(_) | TaskState::None => {
+                if !player_state.storage_unlocked
+                    && slayer_state.points >= STORAGE_UNLOCK_COST + TASK_STORAGE_RESERVE_POINTS
+                {
+                    return SimulationAction::UnlockTaskStorage;
+                }
+                // Only unstore once we can skip the stored task and still have points in reserve
+                if matches!(slayer_state.task_state, TaskState::Completed(_))
+                    && slayer_state.stored_task.is_some()
+                    && slayer_state.points >= 30 + TASK_STORAGE_RESERVE_POINTS
+                {
+                    return SimulationAction::UnstoreTask;
+                }
+            }
+        }
+        MinimizeSlayerLockStrategy::default().select_action(slayer_state, player_state)
+    }
+}
+
 #[derive(Default, Clone, PartialEq, Eq)]
 enum SuperiorsStrategy {
     #[default]
@@ -460,21 +1033,26 @@ enum SuperiorsStrategy {
 }
 
 impl Strategy for SuperiorsStrategy {
+    fn default_goal(&self) -> Goal {
+        // Stop once we have all the superior drops
+        Goal::All(vec![
+            Goal::Item(Item::DustBattlestaff, 1),
+            Goal::Item(Item::MistBattlestaff, 1),
+            Goal::Item(Item::ImbuedHeart, 1),
+            Goal::Item(Item::EternalGem, 1),
+        ])
+    }
+
     fn should_terminate(
         &mut self,
         slayer_state: &SlayerState,
-        _player_state: &PlayerState,
+        player_state: &PlayerState,
     ) -> Option<bool> {
-        // Stop once we have all the superior drops
-        if slayer_state.slayer_data.drops.dust_battlestaff > 0
-            && slayer_state.slayer_data.drops.mist_battlestaff > 0
-            && slayer_state.slayer_data.drops.imbued_heart > 0
-            && slayer_state.slayer_data.drops.eternal_gem > 0
-        {
-            return Some(true);
+        if is_locked_out(slayer_state, player_state) {
+            return Some(false);
         }
         if let TaskState::Active((monster, _, _)) = slayer_state.task_state {
-            if !monster.can_limpwurt_kill()
+            if !player_state.can_kill(monster)
                 && slayer_state.points < 30
                 && Turael.can_assign(monster)
             {
@@ -498,7 +1076,7 @@ impl Strategy for SuperiorsStrategy {
         }
         match (slayer_state.task_state, self.clone()) {
             (TaskState::Active((monster, master, _)), SuperiorsStrategy::AccumulatePoints) => {
-                if monster.can_limpwurt_kill() {
+                if player_state.can_kill(monster) {
                     // Turael-skip Vannaka tasks that are too slow
                     if master == Vannaka {
                         if [
@@ -520,8 +1098,10 @@ impl Strategy for SuperiorsStrategy {
                             SimulationAction::CompleteTask
                         } else if slayer_state.points >= 120 {
                             SimulationAction::PointSkip
-                        } else {
+                        } else if player_state.can_use_master(Turael) {
                             SimulationAction::NewAssignment(Turael)
+                        } else {
+                            SimulationAction::CompleteTask
                         }
                     } else {
                         SimulationAction::CompleteTask
@@ -535,14 +1115,17 @@ impl Strategy for SuperiorsStrategy {
                 } else if slayer_state.points > 120 {
                     SimulationAction::PointSkip
                 } else {
-                    SimulationAction::NewAssignment(Turael)
+                    skip_task(slayer_state, player_state)
                 }
             }
             (TaskState::Active((monster, master, _)), SuperiorsStrategy::GetSuperiors) => {
-                if monster.can_limpwurt_kill() {
+                if player_state.can_kill(monster) {
                     // Turael-skip Vannaka tasks that are too slow
                     if master == Vannaka {
-                        if monster.has_superior() || Turael.can_assign(monster) {
+                        if monster.has_superior()
+                            || Turael.can_assign(monster)
+                            || !player_state.can_use_master(Turael)
+                        {
                             SimulationAction::CompleteTask
                         } else {
                             SimulationAction::NewAssignment(Turael)
@@ -557,7 +1140,7 @@ impl Strategy for SuperiorsStrategy {
                     );
                     SimulationAction::PointSkip
                 } else {
-                    SimulationAction::NewAssignment(Turael)
+                    skip_task(slayer_state, player_state)
                 }
             }
             (TaskState::Completed(_) | TaskState::None, SuperiorsStrategy::AccumulatePoints) => {
@@ -568,12 +1151,11 @@ impl Strategy for SuperiorsStrategy {
                 }
                 // Only do Vannaka tasks every 10 task
                 let streak_after_next_task = slayer_state.task_streak + 1;
-                let next_slayer_master = if streak_after_next_task.is_multiple_of(10) {
-                    Vannaka
+                if streak_after_next_task.is_multiple_of(10) {
+                    next_master(player_state, &[Vannaka, Turael])
                 } else {
-                    Turael
-                };
-                SimulationAction::NewAssignment(next_slayer_master)
+                    next_master(player_state, &[Turael])
+                }
             }
             (TaskState::Completed(_) | TaskState::None, SuperiorsStrategy::GetSuperiors) => {
                 // Check if we should switch back to accumulating points
@@ -581,59 +1163,181 @@ impl Strategy for SuperiorsStrategy {
                     *self = SuperiorsStrategy::AccumulatePoints;
                     return self.select_action(slayer_state, player_state);
                 }
-                SimulationAction::NewAssignment(Vannaka)
+                if !player_state.superiors_unlocked {
+                    return SimulationAction::UnlockSuperiors;
+                }
+                next_master(player_state, &[Vannaka, Spria])
             }
         }
     }
 }
 
-/// Returns the number of tasks received, the minimum/maximum points reached, and whether he escaped (i.e. got lots of points)
-fn simulate_limpwurt<S: Strategy + Clone + Send>(
+/// Runs one simulation from `start` until the strategy terminates it.
+/// Returns the final slayer and player state, and whether the run succeeded
+/// (`false` means Limpwurt got slayer locked).
+///
+/// Observers get `on_start` once before the first action. Every loop then applies the reached
+/// timeline changes (`on_capability_change`), and either ends with `on_termination` or runs one
+/// action, calling its specific callbacks (`on_assignment`, `on_completion`, `on_skip`, ...),
+/// then `on_restock` for every gathering session it started, followed by `on_action`.
+fn simulate_limpwurt<S: Strategy>(
     start: SimulationStartPoint,
+    config: &SimulationConfig,
     mut strategy: S,
+    observers: &mut [&mut dyn SimulationObserver],
 ) -> (SlayerState, PlayerState, bool) {
-    let mut limpwurt =
-        PlayerState::new(start.slayer_exp, start.quests_done, start.storage_unlocked);
+    let combat_levels = start.combat_levels.unwrap_or(LIMPWURT_COMBAT_LEVELS);
+    let mut limpwurt = PlayerState {
+        superiors_unlocked: start.superiors_unlocked,
+        combat_exp: CombatExp::from_levels(&combat_levels),
+        combat_levels,
+        combat_levels_known: start.combat_levels.is_some(),
+        profile: start.profile,
+        ..PlayerState::new(start.slayer_exp, start.quests_done, start.storage_unlocked)
+    };
 
     let mut slayer_state = SlayerState {
         task_streak: start.task_streak,
         points: start.points,
         task_state: start.task_state,
         stored_task: None,
-        slayer_data: SlayerData::default(),
+        slayer_data: SlayerData {
+            inventory: Inventory::with_restocks(config.restock_amounts.clone())
+                .with_stock(start.stock),
+            // A run that never spends or gains points stays at its starting points
+            min_points: start.points as u64,
+            max_points: start.points as u64,
+            ..Default::default()
+        },
         location: Location::SlayerMaster(Turael),
     };
 
-    let mut rng = SmallRng::from_os_rng();
+    let mut rng = match config.rng_seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_os_rng(),
+    };
 
+    for observer in observers.iter_mut() {
+        observer.on_start(&slayer_state, &limpwurt);
+    }
     loop {
+        for change in AccountProfile::take_reached_changes(&slayer_state, &mut limpwurt) {
+            change.apply(&mut limpwurt);
+            for observer in observers.iter_mut() {
+                observer.on_capability_change(&change, &slayer_state);
+            }
+        }
+
         if let Some(result) = strategy.should_terminate(&slayer_state, &limpwurt) {
+            for observer in observers.iter_mut() {
+                observer.on_termination(result, &slayer_state, &limpwurt);
+            }
             return (slayer_state, limpwurt, result);
         }
 
         let action = strategy.select_action(&slayer_state, &limpwurt);
+        let task_before = slayer_state.task_state;
+        let restocks_before = slayer_state.slayer_data.inventory.restocks.len();
 
         match action {
             SimulationAction::CompleteTask => {
-                slayer_state.complete_assignment(&mut rng, &mut limpwurt)
+                let outcome = slayer_state.complete_assignment(&mut rng, &mut limpwurt, config);
+                for observer in observers.iter_mut() {
+                    if outcome.superiors > 0 {
+                        observer.on_superior_spawn(
+                            outcome.master,
+                            outcome.monster,
+                            outcome.superiors,
+                        );
+                    }
+                    for (item, amount) in outcome.drops.iter() {
+                        observer.on_drop(outcome.monster, *item, *amount);
+                    }
+                    observer.on_completion(
+                        outcome.master,
+                        outcome.monster,
+                        outcome.kills,
+                        &slayer_state,
+                        &limpwurt,
+                    );
+                }
+            }
+            SimulationAction::PointSkip => {
+                slayer_state.point_skip();
+                if let TaskState::Active((monster, master, _)) = task_before {
+                    for observer in observers.iter_mut() {
+                        observer.on_skip(master, monster, SkipKind::Points, &slayer_state);
+                    }
+                }
             }
-            SimulationAction::PointSkip => slayer_state.point_skip(),
             SimulationAction::NewAssignment(master) => {
-                slayer_state.new_assignment(&mut rng, master, &limpwurt)
+                slayer_state.new_assignment(&mut rng, master, &limpwurt, config);
+                if let TaskState::Active((skipped_monster, skipped_master, _)) = task_before {
+                    for observer in observers.iter_mut() {
+                        observer.on_skip(
+                            skipped_master,
+                            skipped_monster,
+                            SkipKind::Turael,
+                            &slayer_state,
+                        );
+                    }
+                }
+                if let TaskState::Active((monster, master, amount)) = slayer_state.task_state {
+                    for observer in observers.iter_mut() {
+                        observer.on_assignment(master, monster, amount, &slayer_state);
+                    }
+                }
             }
             SimulationAction::UnlockTaskStorage => {
                 assert!(!limpwurt.storage_unlocked);
                 limpwurt.storage_unlocked = true;
-                assert!(slayer_state.points >= 500);
-                slayer_state.points -= 500;
+                assert!(slayer_state.points >= STORAGE_UNLOCK_COST);
+                slayer_state.points -= STORAGE_UNLOCK_COST;
+                slayer_state.slayer_data.min_points = slayer_state
+                    .slayer_data
+                    .min_points
+                    .min(slayer_state.points as u64);
+            }
+            SimulationAction::UnlockSuperiors => {
+                assert!(!limpwurt.superiors_unlocked);
+                limpwurt.superiors_unlocked = true;
+                assert!(slayer_state.points >= SUPERIOR_UNLOCK_COST);
+                slayer_state.points -= SUPERIOR_UNLOCK_COST;
+                slayer_state.slayer_data.min_points = slayer_state
+                    .slayer_data
+                    .min_points
+                    .min(slayer_state.points as u64);
+                slayer_state.slayer_data.superiors_unlocked_after = Some((
+                    slayer_state
+                        .slayer_data
+                        .total_tasks_received
+                        .values()
+                        .sum::<u64>(),
+                    slayer_state.slayer_data.time_spent(),
+                ));
             }
             SimulationAction::StoreTask => slayer_state.store_task(&limpwurt),
             SimulationAction::UnstoreTask => slayer_state.unstore_task(),
+            SimulationAction::CompleteQuest(quest, time) => {
+                slayer_state.complete_quest(quest, time, &mut limpwurt)
+            }
+        }
+
+        for restock in &slayer_state.slayer_data.inventory.restocks[restocks_before..] {
+            for observer in observers.iter_mut() {
+                observer.on_restock(restock, &slayer_state);
+            }
+        }
+        for observer in observers.iter_mut() {
+            observer.on_action(action, &slayer_state, &limpwurt);
         }
     }
 }
 
-#[derive(EnumIter, Display, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(
+    EnumIter, EnumString, Display, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd,
+)]
+#[strum(ascii_case_insensitive)]
 #[allow(dead_code)]
 enum SlayerMaster {
     Turael,
@@ -670,6 +1374,24 @@ impl SlayerMaster {
             Chaeldar => 10,
         }
     }
+
+    pub fn combat_requirement(&self) -> u8 {
+        match self {
+            Turael => 0,
+            Spria => 0,
+            Vannaka => 40,
+            Chaeldar => 70,
+        }
+    }
+
+    pub fn quest_requirement(&self) -> Option<Quest> {
+        match self {
+            Turael => None,
+            Spria => Some(Quest::PorcineOfInterest),
+            Vannaka => None,
+            Chaeldar => Some(Quest::LostCity),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -705,77 +1427,96 @@ struct SlayerData {
     total_tasks_received: BTreeMap<(SlayerMaster, Monster), u64>,
     total_tasks_done: BTreeMap<(SlayerMaster, Monster), u64>,
     total_kills: BTreeMap<(SlayerMaster, Monster), u64>, // Tracks the number of actual kills, not the number assigned
-    slayer_master_travels: BTreeMap<SlayerMaster, u64>,  // Only tracked for timekeeping
-    num_stored_tasks: u64,                               // Only tracked for timekeeping
-    num_unstored_tasks: u64,                             // Only tracked for timekeeping
+    superior_kills: BTreeMap<(SlayerMaster, Monster), u64>, // Not included in total_kills
+    slayer_master_travels: BTreeMap<SlayerMaster, u64>,
+    num_stored_tasks: u64,
+    num_unstored_tasks: u64,
+    // The time actually spent on each part of the run, as sampled and scaled, so they add up to the time spent
+    master_travel_time: BTreeMap<SlayerMaster, Duration>,
+    task_travel_time: BTreeMap<(SlayerMaster, Monster), Duration>,
+    kill_time: BTreeMap<(SlayerMaster, Monster), Duration>,
+    superior_kill_time: BTreeMap<(SlayerMaster, Monster), Duration>,
+    quest_time: Duration,
     supplies_used: Supplies,
-    drops: SlayerDrops,
+    inventory: Inventory,
+    drops: BTreeMap<Item, u64>,
+    superiors_unlocked_after: Option<(u64, Duration)>, // Tasks received and time spent when buying the unlock
+    elapsed: Duration, // Running total of the time spent, updated together with the counts above
 }
 
 impl SlayerData {
+    /// Total time spent in the run so far.
+    /// Each action adds its own time to this running total, so observers and goals can read it after every action
+    /// without adding up every count again
     pub fn time_spent(&self) -> Duration {
-        let mut total_time = Duration::ZERO;
+        self.elapsed
+    }
 
-        for (master, amount) in self.slayer_master_travels.iter() {
-            total_time += master.travel_time() * *amount as u32;
-        }
-        for ((_master, monster), amount) in self.total_tasks_done.iter() {
-            let monster_data = monster.task_data().unwrap_or_else(|| {
-                panic!(
-                    "No task data for monster {}, needed for time calculation",
-                    monster
-                )
-            });
-            total_time += monster_data.travel_time() * *amount as u32;
-        }
-        for ((_, monster), kills) in self.total_kills.iter() {
-            let monster_data = monster.task_data().unwrap_or_else(|| {
-                panic!(
-                    "No task data for monster {}, needed for time calculation",
-                    monster
-                )
-            });
-            total_time += monster_data.time_per_kill * *kills as u32;
-        }
+    /// Recompute the time spent from the recorded parts, to check that the running total is correct.
+    /// Only used by tests, because `time_spent` is the source of truth
+    #[cfg(test)]
+    pub fn recompute_time_spent(&self) -> Duration {
+        let mut total_time = Duration::ZERO;
+        total_time += self.master_travel_time.values().sum::<Duration>();
+        total_time += self.task_travel_time.values().sum::<Duration>();
+        total_time += self.kill_time.values().sum::<Duration>();
+        total_time += self.superior_kill_time.values().sum::<Duration>();
         total_time += STORE_TASK_TIME * self.num_stored_tasks as u32;
         total_time += UNSTORE_TASK_TIME * self.num_unstored_tasks as u32;
+        total_time += self.quest_time;
 
-        total_time += self.supplies_used.time_to_gather();
+        total_time += self.gathering_time();
+        total_time += self.inventory.worn.equip_time();
         total_time
     }
 
+    pub fn use_supplies(
+        &mut self,
+        supplies: Supplies,
+        player_state: &PlayerState,
+        config: &SimulationConfig,
+    ) {
+        self.elapsed += self
+            .inventory
+            .take(&supplies, player_state, config, self.elapsed);
+        self.supplies_used = self.supplies_used.clone() + supplies;
+    }
+
+    /// Time spent gathering supplies
+    pub fn gathering_time(&self) -> Duration {
+        self.inventory.gathering_time()
+    }
+
+    pub fn superior_slayer_exp(&self) -> u64 {
+        self.superior_kills
+            .iter()
+            .map(|((_, monster), kills)| {
+                monster.task_data().unwrap().superior.unwrap().slayer_exp as u64 * kills
+            })
+            .sum()
+    }
+
     pub fn print_time_data(&self) {
-        let mut slayer_master_travel: BTreeMap<SlayerMaster, Duration> = BTreeMap::new();
-        for (master, amount) in self.slayer_master_travels.iter() {
-            *slayer_master_travel.entry(*master).or_default() +=
-                master.travel_time() * *amount as u32;
-        }
         println!("Slayer master travel time: ");
-        for (master, time) in slayer_master_travel {
+        for (master, time) in &self.master_travel_time {
             println!("{:17} {:5.1} hours", master, time.as_secs_f64() / 3600.0);
         }
         println!();
 
-        let mut slayer_monster_travel_time: BTreeMap<Monster, Duration> = BTreeMap::new();
-        for ((_master, monster), amount) in self.total_tasks_done.iter() {
-            let monster_data = monster.task_data().unwrap();
-            *slayer_monster_travel_time.entry(*monster).or_default() +=
-                monster_data.travel_time() * *amount as u32;
-        }
         println!("Slayer monster travel time: ");
-        for (monster, time) in slayer_monster_travel_time {
+        for (monster, time) in per_monster(&self.task_travel_time) {
             println!("{:17} {:5.1} hours", monster, time.as_secs_f64() / 3600.0);
         }
         println!();
 
-        let mut monster_kill_time: BTreeMap<Monster, Duration> = BTreeMap::new();
-        for ((_, monster), kills) in self.total_kills.iter() {
-            let monster_data = monster.task_data().unwrap();
-            *monster_kill_time.entry(*monster).or_default() +=
-                monster_data.time_per_kill * *kills as u32;
-        }
         println!("Slayer monster kill time: ");
-        for (monster, time) in monster_kill_time {
+        for (monster, time) in per_monster(&self.kill_time) {
+            println!("{:17} {:5.1} hours", monster, time.as_secs_f64() / 3600.0);
+        }
+        println!();
+
+        println!("Superior kill time: ");
+        for (monster, time) in per_monster(&self.superior_kill_time) {
             println!("{:17} {:5.1} hours", monster, time.as_secs_f64() / 3600.0);
         }
         println!();
@@ -787,52 +1528,58 @@ impl SlayerData {
                 .as_secs_f32()
                 / 3600.0
         );
+        println!(
+            "Quest time: {:.1} hours",
+            self.quest_time.as_secs_f32() / 3600.0
+        );
+        println!(
+            "Equipping new jewellery: {:.1} hours",
+            self.inventory.worn.equip_time().as_secs_f32() / 3600.0
+        );
 
         println!();
-        self.supplies_used.print_time_breakdown();
+        self.inventory.print_time_breakdown();
 
         println!(
             "Total supplies gathering time: {:.1} hours",
-            self.supplies_used.time_to_gather().as_secs_f32() / 3600.0
+            self.gathering_time().as_secs_f32() / 3600.0
         );
+        if !self.inventory.restock_amounts.is_empty() {
+            println!();
+            self.inventory.print_restocks();
+        }
     }
 
     pub fn print_time_data_short(&self) {
         println!("Slayer master travel time: ");
 
-        for (master, amount) in self.slayer_master_travels.iter() {
-            println!(
-                "{:17} {:5.1} hours",
-                master,
-                (master.travel_time() * *amount as u32).as_secs_f64() / 3600.0
-            );
+        for (master, time) in &self.master_travel_time {
+            println!("{:17} {:5.1} hours", master, time.as_secs_f64() / 3600.0);
         }
         println!();
 
-        let mut slayer_monster_travel_time: BTreeMap<SlayerMaster, Duration> = BTreeMap::new();
-        for ((master, monster), amount) in self.total_tasks_done.iter() {
-            let monster_data = monster.task_data().unwrap();
-            *slayer_monster_travel_time.entry(*master).or_default() +=
-                monster_data.travel_time() * *amount as u32;
-        }
         println!("Slayer task travel time: ");
-        for (monster, time) in slayer_monster_travel_time {
+        for (monster, time) in per_master(&self.task_travel_time) {
             println!("{:17} {:5.1} hours", monster, time.as_secs_f64() / 3600.0);
         }
         println!();
 
-        let mut monster_kill_time: BTreeMap<SlayerMaster, Duration> = BTreeMap::new();
-        for ((master, monster), kills) in self.total_kills.iter() {
-            let monster_data = monster.task_data().unwrap();
-            *monster_kill_time.entry(*master).or_default() +=
-                monster_data.time_per_kill * *kills as u32;
-        }
         println!("Slayer task kill time: ");
-        for (master, time) in monster_kill_time {
+        for (master, time) in per_master(&self.kill_time) {
             println!("{:10} {:5.1} hours", master, time.as_secs_f64() / 3600.0);
         }
         println!();
 
+        println!(
+            "Superior kill time: {:.1} hours",
+            self.superior_kill_time
+                .values()
+                .sum::<Duration>()
+                .as_secs_f32()
+                / 3600.0
+        );
+        println!();
+
         println!(
             "Store/unstore task time: {:.1} hours",
             (STORE_TASK_TIME * self.num_stored_tasks as u32
@@ -840,14 +1587,46 @@ impl SlayerData {
                 .as_secs_f32()
                 / 3600.0
         );
+        println!(
+            "Quest time: {:.1} hours",
+            self.quest_time.as_secs_f32() / 3600.0
+        );
+        println!(
+            "Equipping new jewellery: {:.1} hours",
+            self.inventory.worn.equip_time().as_secs_f32() / 3600.0
+        );
 
         println!();
 
         println!(
             "Total supplies gathering time: {:.1} hours",
-            self.supplies_used.time_to_gather().as_secs_f32() / 3600.0
+            self.gathering_time().as_secs_f32() / 3600.0
         );
+        if !self.inventory.restock_amounts.is_empty() {
+            println!();
+            self.inventory.print_restocks();
+        }
+    }
+}
+
+/// Add up `times` per monster, over all slayer masters
+fn per_monster(times: &BTreeMap<(SlayerMaster, Monster), Duration>) -> BTreeMap<Monster, Duration> {
+    let mut per_monster: BTreeMap<Monster, Duration> = BTreeMap::new();
+    for ((_, monster), time) in times {
+        *per_monster.entry(*monster).or_default() += *time;
+    }
+    per_monster
+}
+
+/// Add up `times` per slayer master, over all monsters
+fn per_master(
+    times: &BTreeMap<(SlayerMaster, Monster), Duration>,
+) -> BTreeMap<SlayerMaster, Duration> {
+    let mut per_master: BTreeMap<SlayerMaster, Duration> = BTreeMap::new();
+    for ((master, _), time) in times {
+        *per_master.entry(*master).or_default() += *time;
     }
+    per_master
 }
 
 impl Default for SlayerData {
@@ -859,16 +1638,42 @@ impl Default for SlayerData {
             total_tasks_received: BTreeMap::new(),
             total_tasks_done: BTreeMap::new(),
             total_kills: BTreeMap::new(),
+            superior_kills: BTreeMap::new(),
             slayer_master_travels: BTreeMap::new(),
             num_stored_tasks: 0,
             num_unstored_tasks: 0,
+            master_travel_time: BTreeMap::new(),
+            task_travel_time: BTreeMap::new(),
+            kill_time: BTreeMap::new(),
+            superior_kill_time: BTreeMap::new(),
+            quest_time: Duration::ZERO,
             supplies_used: Supplies::default(),
-            drops: SlayerDrops::default(),
+            inventory: Inventory::default(),
+            drops: BTreeMap::new(),
+            superiors_unlocked_after: None,
+            elapsed: Duration::ZERO,
         }
     }
 }
 
-#[derive(Default, Clone, Debug)]
+/// A supply that is used up during a run, named after its field in `Supplies`
+#[derive(
+    EnumIter, EnumString, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[strum(serialize_all = "snake_case")]
+enum SupplyKind {
+    ExpeditiousBraceletCharges,
+    BraceletOfSlaughterCharges,
+    GamesNecklaceCharges,
+    DuelingRingCharges,
+    NecklaceOfPassageCharges,
+    ChronicleCharges,
+    SkullSceptreCharges,
+    GiantsoulAmuletCharges,
+    LawRunes,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 struct Supplies {
     expeditious_bracelet_charges: u64,
     bracelet_of_slaughter_charges: u64,
@@ -902,27 +1707,6 @@ impl ops::Add for Supplies {
     }
 }
 
-#[derive(Default, Clone, PartialEq, Eq, Debug)]
-struct SlayerDrops {
-    dust_battlestaff: u64,
-    mist_battlestaff: u64,
-    imbued_heart: u64,
-    eternal_gem: u64,
-}
-
-impl ops::Add for SlayerDrops {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            dust_battlestaff: self.dust_battlestaff + rhs.dust_battlestaff,
-            mist_battlestaff: self.mist_battlestaff + rhs.mist_battlestaff,
-            imbued_heart: self.imbued_heart + rhs.imbued_heart,
-            eternal_gem: self.eternal_gem + rhs.eternal_gem,
-        }
-    }
-}
-
 #[derive(Clone, PartialEq, Eq)]
 enum Location {
     SlayerMaster(SlayerMaster),
@@ -935,6 +1719,15 @@ impl Default for Location {
     }
 }
 
+/// What happened while completing a single task
+struct TaskOutcome {
+    master: SlayerMaster,
+    monster: Monster,
+    kills: u64, // Not counting superiors
+    superiors: u64,
+    drops: BTreeMap<Item, u64>,
+}
+
 #[derive(Clone, Default)]
 struct SlayerState {
     points: u32,
@@ -951,15 +1744,13 @@ impl SlayerState {
         rng: &mut R,
         master: SlayerMaster,
         player_state: &PlayerState,
+        config: &SimulationConfig,
     ) {
-        match master {
-            Turael => (),
-            Spria => {
-                assert!(player_state.quests_done.contains(&Quest::PorcineOfInterest))
-            }
-            Vannaka => (),
-            Chaeldar => assert!(player_state.quests_done.contains(&Quest::LostCity)),
-        }
+        assert!(
+            player_state.can_use_master(master),
+            "{} is not in the profile, or its quest or combat requirement is not met",
+            master
+        );
 
         if self.location != Location::SlayerMaster(master) {
             *self
@@ -967,9 +1758,19 @@ impl SlayerState {
                 .slayer_master_travels
                 .entry(master)
                 .or_default() += 1;
+            let travel_time = config.cost_factors.scale(
+                Parameter::MasterTravel(master),
+                config.duration_model.sample(master.travel_time(), rng),
+            );
+            *self
+                .slayer_data
+                .master_travel_time
+                .entry(master)
+                .or_default() += travel_time;
+            self.slayer_data.elapsed += travel_time;
 
-            self.slayer_data.supplies_used =
-                self.slayer_data.supplies_used.clone() + master.travel_cost();
+            self.slayer_data
+                .use_supplies(master.travel_cost(), player_state, config);
         }
         self.location = Location::SlayerMaster(master);
 
@@ -1043,6 +1844,18 @@ impl SlayerState {
         self.stored_task = Some((monster, master, amount));
         self.task_state = TaskState::None;
         self.slayer_data.num_stored_tasks += 1;
+        self.slayer_data.elapsed += STORE_TASK_TIME;
+    }
+
+    pub fn complete_quest(&mut self, quest: Quest, time: Duration, player_state: &mut PlayerState) {
+        assert!(
+            !player_state.quests_done.contains(&quest),
+            "{} is already completed",
+            quest
+        );
+        player_state.quests_done.push(quest);
+        self.slayer_data.quest_time += time;
+        self.slayer_data.elapsed += time;
     }
 
     pub fn unstore_task(&mut self) {
@@ -1054,12 +1867,25 @@ impl SlayerState {
         };
         self.task_state = TaskState::Active((monster, master, amount));
         self.slayer_data.num_unstored_tasks += 1;
+        self.slayer_data.elapsed += UNSTORE_TASK_TIME;
     }
 
-    pub fn complete_assignment<R: Rng>(&mut self, rng: &mut R, player_state: &mut PlayerState) {
+    pub fn complete_assignment<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        player_state: &mut PlayerState,
+        config: &SimulationConfig,
+    ) -> TaskOutcome {
         let TaskState::Active((monster, master, amount)) = self.task_state else {
             panic!("Cannot complete assignment when no task is active");
         };
+        let mut outcome = TaskOutcome {
+            master,
+            monster,
+            kills: 0,
+            superiors: 0,
+            drops: BTreeMap::new(),
+        };
         self.task_streak += 1;
         *self
             .slayer_data
@@ -1075,67 +1901,123 @@ impl SlayerState {
             )
         });
 
-        self.slayer_data.supplies_used =
-            self.slayer_data.supplies_used.clone() + task_data.travel_supplies.clone();
+        let travel_time = config.cost_factors.scale(
+            Parameter::TravelSteps(monster),
+            config.duration_model.sample(task_data.travel_time(), rng),
+        );
+        *self
+            .slayer_data
+            .task_travel_time
+            .entry((master, monster))
+            .or_default() += travel_time;
+        self.slayer_data.elapsed += travel_time;
+        self.slayer_data
+            .use_supplies(task_data.travel_supplies.clone(), player_state, config);
+        let mut bracelet_charges = Supplies::default();
 
-        let superior_rare_drop_chance = task_data.superior_unique_drop_rate;
+        let can_spawn_superior = task_data.superior.is_some() && player_state.superiors_unlocked;
 
-        // If the monster has a superior, or we're using a slayer bracelet, simulate each individual kill
-        if superior_rare_drop_chance.is_some()
+        // If the monster can spawn a superior, has drops we track, or we're using a slayer bracelet, simulate each individual kill
+        if can_spawn_superior
+            || task_data.drop_table.is_some()
             || task_data.use_bracelet_of_slaughter
             || task_data.use_expeditious_bracelet
         {
             let mut kills_left: u32 = amount;
             while kills_left > 0 {
-                *self
-                    .slayer_data
-                    .total_kills
-                    .entry((master, monster))
-                    .or_default() += 1;
+                outcome.kills += 1;
                 player_state.slayer_exp += monster.slayer_exp();
 
                 if task_data.use_bracelet_of_slaughter && rng.random::<f32>() < 0.25 {
-                    self.slayer_data.supplies_used.bracelet_of_slaughter_charges += 1;
+                    bracelet_charges.bracelet_of_slaughter_charges += 1;
                     kills_left += 1; // The kill is subtracted later
                 }
                 if task_data.use_expeditious_bracelet && rng.random::<f32>() < 0.25 {
-                    self.slayer_data.supplies_used.expeditious_bracelet_charges += 1;
+                    bracelet_charges.expeditious_bracelet_charges += 1;
                     kills_left -= 1;
                 }
 
-                if let Some(superior_rare_drop_chance) = superior_rare_drop_chance
-                    && rng.random::<f32>() < (1.0 / 200.0)
+                if let Some(superior) = &task_data.superior
+                    && can_spawn_superior
+                    && rng.random::<f32>() < config.superior_spawn_rate
                 {
                     kills_left = kills_left.saturating_sub(1); // The superior counts as an extra kill
-                    let main_roll = rng.random::<f32>();
-                    if main_roll < superior_rare_drop_chance {
-                        let udt_roll = rng.random::<f32>();
-                        if udt_roll < 1.0 / 2.286 {
-                            self.slayer_data.drops.dust_battlestaff += 1;
-                        } else if udt_roll < 2.0 / 2.286 {
-                            self.slayer_data.drops.mist_battlestaff += 1;
-                        } else {
-                            self.slayer_data.drops.imbued_heart += 1;
-                        }
-                    } else if main_roll < 2.0 * superior_rare_drop_chance {
-                        let udt_roll = rng.random::<f32>();
-                        if udt_roll < 1.0 / 8.0 {
-                            self.slayer_data.drops.eternal_gem += 1;
-                        }
-                    }
+                    outcome.superiors += 1;
+                    player_state.slayer_exp += superior.slayer_exp;
+                    superior.drop_table.roll(rng, &mut outcome.drops);
+                }
+                if let Some(drop_table) = task_data.drop_table {
+                    drop_table.roll(rng, &mut outcome.drops);
                 }
                 kills_left = kills_left.saturating_sub(1);
             }
         } else {
+            outcome.kills = amount as u64;
+            player_state.slayer_exp += monster.slayer_exp() * amount;
+        }
+        *self
+            .slayer_data
+            .total_kills
+            .entry((master, monster))
+            .or_default() += outcome.kills;
+        let kill_time = config.cost_factors.scale(
+            Parameter::KillTime(monster),
+            config.duration_model.sample_sum(
+                player_state.scale_kill_time(monster, task_data.kill_time(player_state, config)),
+                outcome.kills,
+                rng,
+            ),
+        );
+        *self
+            .slayer_data
+            .kill_time
+            .entry((master, monster))
+            .or_default() += kill_time;
+        self.slayer_data.elapsed += kill_time;
+        if let Some(superior) = &task_data.superior
+            && outcome.superiors > 0
+        {
             *self
                 .slayer_data
-                .total_kills
+                .superior_kills
                 .entry((master, monster))
-                .or_default() += amount as u64;
-            player_state.slayer_exp += monster.slayer_exp() * amount;
+                .or_default() += outcome.superiors;
+            let superior_kill_time = config.cost_factors.scale(
+                Parameter::KillTime(monster),
+                config.duration_model.sample_sum(
+                    player_state.scale_kill_time(monster, superior.kill_time(player_state, config)),
+                    outcome.superiors,
+                    rng,
+                ),
+            );
+            *self
+                .slayer_data
+                .superior_kill_time
+                .entry((master, monster))
+                .or_default() += superior_kill_time;
+            self.slayer_data.elapsed += superior_kill_time;
         }
+        self.slayer_data
+            .use_supplies(bracelet_charges, player_state, config);
+        for (item, amount) in outcome.drops.iter() {
+            *self.slayer_data.drops.entry(*item).or_default() += amount;
+        }
+        self.slayer_data.inventory.credit(&outcome.drops);
         player_state.slayer_level = data::level_for_exp(player_state.slayer_exp);
 
+        // Every kill also gives combat exp for the damage dealt, which is the monster's hitpoints
+        let hitpoints = task_data
+            .defence
+            .map_or(monster.slayer_exp(), |defence| defence.hitpoints);
+        let mut damage = hitpoints as u64 * outcome.kills;
+        if let Some(superior) = &task_data.superior {
+            damage += superior.hitpoints as u64 * outcome.superiors;
+        }
+        player_state
+            .combat_exp
+            .add_damage(damage, config.combat_skill);
+        player_state.combat_levels = CombatLevels::from_exp(&player_state.combat_exp);
+
         if self.task_streak >= 5 {
             let point_multiplier = if self.task_streak.is_multiple_of(1000) {
                 50
@@ -1156,6 +2038,7 @@ impl SlayerState {
             self.slayer_data.max_points = self.slayer_data.max_points.max(self.points as u64);
         }
         self.task_state = TaskState::Completed(monster);
+        outcome
     }
 
     pub fn point_skip(&mut self) {
@@ -1175,6 +2058,12 @@ struct PlayerState {
     slayer_level: u8,
     quests_done: Vec<Quest>,
     storage_unlocked: bool,
+    superiors_unlocked: bool, // Superiors only spawn once "Bigger and Badder" is bought, like in game
+    combat_exp: CombatExp,
+    combat_levels: CombatLevels, // Always the levels of `combat_exp`
+    combat_levels_known: bool,   // Whether the levels started from the account's real levels
+    kill_time_factors: BTreeMap<Monster, f32>, // From kill_time changes on the account's timeline
+    profile: AccountProfile,
 }
 
 impl PlayerState {
@@ -1184,6 +2073,12 @@ impl PlayerState {
             slayer_level: data::level_for_exp(slayer_exp),
             quests_done,
             storage_unlocked,
+            superiors_unlocked: false,
+            combat_exp: CombatExp::default(),
+            combat_levels: CombatLevels::default(),
+            combat_levels_known: false,
+            kill_time_factors: BTreeMap::new(),
+            profile: AccountProfile::default(),
         }
     }
 
@@ -1197,6 +2092,45 @@ impl PlayerState {
                 .quest_requirement
                 .is_none_or(|quest| self.quests_done.contains(&quest))
     }
+
+    pub fn can_kill(&self, monster: Monster) -> bool {
+        self.profile.can_kill(monster)
+    }
+
+    /// Factor for the time per kill of `monster`, from the changes on the account's timeline so far
+    pub fn kill_time_factor(&self, monster: Monster) -> f32 {
+        self.kill_time_factors.get(&monster).copied().unwrap_or(1.0)
+    }
+
+    /// Scale `kill_time`, the time per kill of `monster` or its superior, by the changes on the account's timeline so far
+    pub fn scale_kill_time(&self, monster: Monster, kill_time: Duration) -> Duration {
+        let factor = self.kill_time_factor(monster);
+        // Multiplying by exactly 1 would still round the duration through an f32
+        if factor == 1.0 {
+            kill_time
+        } else {
+            kill_time.mul_f32(factor)
+        }
+    }
+
+    /// Combat requirements are only checked when the combat levels are known, so made-up levels never lock out a master
+    pub fn can_use_master(&self, master: SlayerMaster) -> bool {
+        self.profile.can_reach(master)
+            && master
+                .quest_requirement()
+                .is_none_or(|quest| self.quests_done.contains(&quest))
+            && (!self.combat_levels_known
+                || self.combat_levels.combat_level() >= master.combat_requirement())
+    }
+
+    /// The first usable master in `preferred`, or else any usable master
+    pub fn usable_master(&self, preferred: &[SlayerMaster]) -> Option<SlayerMaster> {
+        preferred
+            .iter()
+            .copied()
+            .chain(SlayerMaster::iter())
+            .find(|master| self.can_use_master(*master))
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -1207,7 +2141,10 @@ struct Assignment {
     weight: u32,
 }
 
-#[derive(EnumIter, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    EnumIter, EnumString, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[strum(ascii_case_insensitive)]
 enum Monster {
     AberrantSpectres,
     AbyssalDemons,
@@ -1294,7 +2231,8 @@ enum Monster {
     Zombies,
 }
 
-#[derive(Display, Clone, Copy, PartialEq, Eq)]
+#[derive(EnumString, Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(ascii_case_insensitive)]
 enum Quest {
     ActualVampyreSlayer,
     CabinFever,
@@ -1318,6 +2256,7 @@ enum Quest {
     ReptileGotRipped,
     RumDeal,
     ShadowsOfCustodia,
+    ShiloVillage,
     SkippyAndTheMogres,
     RoyalTrouble,
     WarpedReality,
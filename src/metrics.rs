@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    PlayerState, SlayerData, SlayerMaster,
+    costs::{STORE_TASK_TIME, UNSTORE_TASK_TIME},
+    trajectory::{PERCENTILES, percentile},
+};
+
+/// Every metric of a single simulation, by name. The names are the same, in the same order, for every run
+/// started from the same point. The combat level is left out when the combat levels are not known
+pub fn run_metrics(slayer_data: &SlayerData, player_state: &PlayerState) -> Vec<(String, f64)> {
+    let hours = |duration: Duration| duration.as_secs_f64() / 3600.0;
+    let mut metrics = vec![
+        ("total_points".to_string(), slayer_data.total_points as f64),
+        ("min_points".to_string(), slayer_data.min_points as f64),
+        ("max_points".to_string(), slayer_data.max_points as f64),
+        ("slayer_exp".to_string(), player_state.slayer_exp as f64),
+    ];
+    if player_state.combat_levels_known {
+        metrics.push((
+            "combat_level".to_string(),
+            player_state.combat_levels.combat_level() as f64,
+        ));
+    }
+    metrics.push((
+        "superior_kills".to_string(),
+        slayer_data.superior_kills.values().sum::<u64>() as f64,
+    ));
+
+    for master in SlayerMaster::iter() {
+        let per_master = |counts: &BTreeMap<_, u64>| {
+            counts
+                .iter()
+                .filter(|((task_master, _), _)| *task_master == master)
+                .map(|(_, count)| *count)
+                .sum::<u64>() as f64
+        };
+        metrics.push((
+            format!("{}_tasks_received", master),
+            per_master(&slayer_data.total_tasks_received),
+        ));
+        metrics.push((
+            format!("{}_tasks_done", master),
+            per_master(&slayer_data.total_tasks_done),
+        ));
+        metrics.push((
+            format!("{}_kills", master),
+            per_master(&slayer_data.total_kills),
+        ));
+    }
+
+    for (name, amount) in slayer_data.supplies_used.counts() {
+        metrics.push((name.to_string(), amount as f64));
+    }
+
+    let master_travel_time: Duration = slayer_data.master_travel_time.values().sum();
+    let task_travel_time: Duration = slayer_data.task_travel_time.values().sum();
+    let kill_time: Duration = slayer_data.kill_time.values().sum();
+    let superior_kill_time: Duration = slayer_data.superior_kill_time.values().sum();
+    let storage_time = STORE_TASK_TIME * slayer_data.num_stored_tasks as u32
+        + UNSTORE_TASK_TIME * slayer_data.num_unstored_tasks as u32;
+    metrics.extend([
+        ("master_travel_hours".to_string(), hours(master_travel_time)),
+        ("task_travel_hours".to_string(), hours(task_travel_time)),
+        ("kill_hours".to_string(), hours(kill_time)),
+        ("superior_kill_hours".to_string(), hours(superior_kill_time)),
+        ("store_unstore_hours".to_string(), hours(storage_time)),
+        ("quest_hours".to_string(), hours(slayer_data.quest_time)),
+        (
+            "equip_jewellery_hours".to_string(),
+            hours(slayer_data.inventory.worn.equip_time()),
+        ),
+        (
+            "supplies_gathering_hours".to_string(),
+            hours(slayer_data.gathering_time()),
+        ),
+        ("total_hours".to_string(), hours(slayer_data.time_spent())),
+    ]);
+    metrics
+}
+
+/// Sorted values of the metric with index `metric`, over all runs
+fn sorted_values(runs: &[Vec<(String, f64)>], metric: usize) -> Vec<f64> {
+    let mut values: Vec<f64> = runs.iter().map(|metrics| metrics[metric].1).collect();
+    values.sort_by(f64::total_cmp);
+    values
+}
+
+/// Print the percentiles of each metric, computed independently of each other
+pub fn print_metric_medians(runs: &[Vec<(String, f64)>]) {
+    let Some(first_run) = runs.first() else {
+        return;
+    };
+    print!("{:32}", "Metric");
+    for percentile in PERCENTILES {
+        print!(" {:>10}", format!("p{}", (percentile * 100.0) as u32));
+    }
+    println!();
+    for (metric, (name, _)) in first_run.iter().enumerate() {
+        let values = sorted_values(runs, metric);
+        if values.last() == Some(&0.0) {
+            continue;
+        }
+        print!("{:32}", name);
+        for p in PERCENTILES {
+            print!(" {:>10.1}", percentile(&values, p));
+        }
+        println!();
+    }
+}
+
+/// The index of the run closest to the median of every metric, where each metric is scaled by its
+/// interquartile range. Metrics that barely vary between runs are ignored
+pub fn most_representative_run(runs: &[Vec<(String, f64)>]) -> Option<usize> {
+    let first_run = runs.first()?;
+    let scales: Vec<(f64, f64)> = (0..first_run.len())
+        .map(|metric| {
+            let values = sorted_values(runs, metric);
+            (
+                percentile(&values, 0.5),
+                percentile(&values, 0.75) - percentile(&values, 0.25),
+            )
+        })
+        .collect();
+
+    let distance = |metrics: &Vec<(String, f64)>| {
+        metrics
+            .iter()
+            .zip(scales.iter())
+            .filter(|(_, (_, range))| *range > 0.0)
+            .map(|((_, value), (median, range))| ((value - median) / range).powi(2))
+            .sum::<f64>()
+    };
+    (0..runs.len()).min_by(|a, b| distance(&runs[*a]).total_cmp(&distance(&runs[*b])))
+}
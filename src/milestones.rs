@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    Monster, PlayerState, SlayerMaster, SlayerState,
+    drops::Item,
+    observer::SimulationObserver,
+    trajectory::{PERCENTILES, percentile},
+};
+
+const POINT_THRESHOLDS: [u32; 2] = [500, 1000];
+/// Task streaks that give bonus points
+const STREAK_MILESTONES: [u32; 5] = [10, 50, 100, 250, 1000];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Milestone {
+    SlayerLevel(u8),
+    Points(u32),
+    TaskStreak(u32),
+    FirstDrop(Item),
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Milestone::SlayerLevel(level) => write!(f, "Level {}", level),
+            Milestone::Points(points) => write!(f, "{} points", points),
+            Milestone::TaskStreak(streak) => write!(f, "Streak of {} tasks", streak),
+            Milestone::FirstDrop(item) => write!(f, "First {}", item),
+        }
+    }
+}
+
+/// Records when each milestone is first reached in a single simulation
+pub struct MilestoneTracker {
+    start_level: u8, // Levels up to this one were reached before the simulation started
+    /// Tasks completed and time spent when the milestone was first reached
+    pub reached: BTreeMap<Milestone, (u64, Duration)>,
+}
+
+impl MilestoneTracker {
+    pub fn new(start_level: u8) -> Self {
+        Self {
+            start_level,
+            reached: BTreeMap::new(),
+        }
+    }
+
+    fn reach(&mut self, milestone: Milestone, tasks: u64, elapsed: Duration) {
+        self.reached.entry(milestone).or_insert((tasks, elapsed));
+    }
+}
+
+impl SimulationObserver for MilestoneTracker {
+    fn on_completion(
+        &mut self,
+        _master: SlayerMaster,
+        _monster: Monster,
+        _kills: u64,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) {
+        let slayer_data = &slayer_state.slayer_data;
+        let tasks = slayer_data.total_tasks_done.values().sum::<u64>();
+        let elapsed = slayer_data.time_spent();
+
+        // A single task can give several levels
+        for level in self.start_level + 1..=player_state.slayer_level() {
+            self.reach(Milestone::SlayerLevel(level), tasks, elapsed);
+        }
+        for points in POINT_THRESHOLDS {
+            if slayer_state.points >= points {
+                self.reach(Milestone::Points(points), tasks, elapsed);
+            }
+        }
+        for streak in STREAK_MILESTONES {
+            if slayer_state.task_streak >= streak {
+                self.reach(Milestone::TaskStreak(streak), tasks, elapsed);
+            }
+        }
+        for item in Item::iter() {
+            if slayer_data.drops.contains_key(&item) {
+                self.reach(Milestone::FirstDrop(item), tasks, elapsed);
+            }
+        }
+    }
+}
+
+/// Print the distribution of hours and tasks until each milestone, over the runs that reached it
+pub fn print_milestone_report(trackers: &[MilestoneTracker]) {
+    let mut all_reached: BTreeMap<Milestone, Vec<(u64, Duration)>> = BTreeMap::new();
+    for tracker in trackers {
+        for (milestone, reached) in tracker.reached.iter() {
+            all_reached.entry(*milestone).or_default().push(*reached);
+        }
+    }
+
+    print!("{:28} {:>7}", "Milestone", "runs");
+    for percentile in PERCENTILES {
+        print!(" {:>7}", format!("p{}", (percentile * 100.0) as u32));
+    }
+    println!(" {:>12}", "median tasks");
+    for (milestone, reached) in all_reached {
+        let mut hours: Vec<f32> = reached
+            .iter()
+            .map(|(_, time)| time.as_secs_f32() / 3600.0)
+            .collect();
+        hours.sort_by(f32::total_cmp);
+        let mut tasks: Vec<u64> = reached.iter().map(|(tasks, _)| *tasks).collect();
+        tasks.sort();
+
+        print!(
+            "{:28} {:>6.1}%",
+            milestone.to_string(),
+            100.0 * reached.len() as f32 / trackers.len() as f32
+        );
+        for p in PERCENTILES {
+            print!(" {:>6.1}h", percentile(&hours, p));
+        }
+        println!(" {:>12}", tasks[tasks.len() / 2]);
+    }
+}
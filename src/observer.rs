@@ -0,0 +1,72 @@
+use crate::{
+    Monster, PlayerState, SimulationAction, SlayerMaster, SlayerState, drops::Item,
+    inventory::Restock, profile::CapabilityChange,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipKind {
+    Points,
+    Turael,
+}
+
+/// Callbacks for collecting custom statistics during a simulation.
+/// Every callback is called after the event has been applied to the state, and does nothing by default
+pub trait SimulationObserver {
+    /// Called once with the start state, before the first action
+    fn on_start(&mut self, _slayer_state: &SlayerState, _player_state: &PlayerState) {}
+
+    fn on_assignment(
+        &mut self,
+        _master: SlayerMaster,
+        _monster: Monster,
+        _amount: u32,
+        _slayer_state: &SlayerState,
+    ) {
+    }
+
+    fn on_completion(
+        &mut self,
+        _master: SlayerMaster,
+        _monster: Monster,
+        _kills: u64,
+        _slayer_state: &SlayerState,
+        _player_state: &PlayerState,
+    ) {
+    }
+
+    fn on_skip(
+        &mut self,
+        _master: SlayerMaster,
+        _monster: Monster,
+        _kind: SkipKind,
+        _slayer_state: &SlayerState,
+    ) {
+    }
+
+    fn on_superior_spawn(&mut self, _master: SlayerMaster, _monster: Monster, _amount: u64) {}
+
+    fn on_drop(&mut self, _monster: Monster, _item: Item, _amount: u64) {}
+
+    /// Called for every gathering session started by an action, before `on_action`
+    fn on_restock(&mut self, _restock: &Restock, _slayer_state: &SlayerState) {}
+
+    /// Called when a change on the account's timeline is applied, before the next action
+    fn on_capability_change(&mut self, _change: &CapabilityChange, _slayer_state: &SlayerState) {}
+
+    /// Called after every action, after any of the more specific callbacks
+    fn on_action(
+        &mut self,
+        _action: SimulationAction,
+        _slayer_state: &SlayerState,
+        _player_state: &PlayerState,
+    ) {
+    }
+
+    fn on_termination(
+        &mut self,
+        _success: bool,
+        _slayer_state: &SlayerState,
+        _player_state: &PlayerState,
+    ) {
+    }
+}
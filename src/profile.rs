@@ -0,0 +1,282 @@
+use std::{collections::BTreeSet, fmt, fs, path::Path, str::FromStr};
+
+use strum::{Display, EnumString, IntoEnumIterator};
+
+use crate::{
+    Monster, PlayerState, Quest, SlayerMaster, SlayerState, Supplies, SupplyKind, goals::Goal,
+};
+
+/// A slayer reward that the account may already own
+#[derive(EnumString, Display, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[strum(ascii_case_insensitive)]
+pub enum Unlock {
+    TaskStorage,
+    Superiors,
+}
+
+/// What a restricted account is able to do. Can be parsed from a file with one `key = values` line per field,
+/// where values are separated by commas and `#` starts a comment. A key can be repeated to continue a long list:
+///
+/// ```text
+/// monsters = Bats, Cows, HillGiants
+/// masters = Turael, Spria
+/// teleports = games_necklace_charges, law_runes
+/// unlocks = Superiors
+/// ```
+///
+/// Teleports are named like their field in `Supplies`. Monsters and slayer masters that need an unavailable teleport
+/// to reach can't be used.
+///
+/// Lines on the form `when <goal> then <key> = <values>` are applied during each run, once the goal is reached.
+/// Goals are written like `--goal`. On the timeline, `quests` completes quests,
+/// and `kill_time = Monster:factor` multiplies the time per kill of the monster and its superior:
+///
+/// ```text
+/// when hours=200 then quests = PriestInPeril
+/// when item=ImbuedHeart then kill_time = Trolls:0.8
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountProfile {
+    pub monsters: BTreeSet<Monster>,
+    pub masters: BTreeSet<SlayerMaster>,
+    pub teleports: BTreeSet<SupplyKind>,
+    pub unlocks: BTreeSet<Unlock>,
+    pub timeline: Vec<(Goal, CapabilityChange)>, // Only the changes that are not applied yet
+}
+
+impl Default for AccountProfile {
+    fn default() -> Self {
+        Self::limpwurt()
+    }
+}
+
+impl AccountProfile {
+    /// Limpwurt in the current world state
+    pub fn limpwurt() -> Self {
+        Self {
+            monsters: Monster::iter()
+                .filter(|monster| monster.can_limpwurt_kill())
+                .collect(),
+            masters: SlayerMaster::iter().collect(),
+            teleports: Supplies::teleports().into_iter().collect(),
+            unlocks: BTreeSet::new(),
+            timeline: vec![],
+        }
+    }
+
+    pub fn load(path: &Path) -> Self {
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("Failed to read profile {}: {}", path.display(), err));
+        contents
+            .parse()
+            .unwrap_or_else(|err| panic!("Invalid profile {}: {}", path.display(), err))
+    }
+
+    /// Whether every teleport used by `supplies` is available
+    fn has_teleports(&self, supplies: &Supplies) -> bool {
+        supplies
+            .counts()
+            .all(|(name, amount)| amount == 0 || self.teleports.contains(&name))
+    }
+
+    /// Monsters without task data can't be simulated, so they count as unkillable
+    pub fn can_kill(&self, monster: Monster) -> bool {
+        self.monsters.contains(&monster)
+            && monster
+                .task_data()
+                .is_some_and(|task_data| self.has_teleports(&task_data.travel_supplies))
+    }
+
+    /// Masters are only reachable with the teleport used to travel to them
+    pub fn can_reach(&self, master: SlayerMaster) -> bool {
+        self.masters.contains(&master) && self.has_teleports(&master.travel_cost())
+    }
+
+    /// Remove and return the changes on the timeline whose goal has been reached
+    pub fn take_reached_changes(
+        slayer_state: &SlayerState,
+        player_state: &mut PlayerState,
+    ) -> Vec<CapabilityChange> {
+        let (reached, pending): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut player_state.profile.timeline)
+                .into_iter()
+                .partition(|(goal, _)| goal.is_reached(slayer_state, player_state));
+        player_state.profile.timeline = pending;
+        reached.into_iter().map(|(_, change)| change).collect()
+    }
+}
+
+/// A change to what the account is able to do
+#[derive(Clone, Debug, PartialEq)]
+pub enum CapabilityChange {
+    Monsters(Vec<Monster>),
+    Masters(Vec<SlayerMaster>),
+    Teleports(Vec<SupplyKind>),
+    Unlocks(Vec<Unlock>),
+    Quests(Vec<Quest>),
+    KillTime(Monster, f32), // Factor for the time per kill
+}
+
+impl CapabilityChange {
+    /// Apply the change in the middle of a run
+    pub fn apply(&self, player_state: &mut PlayerState) {
+        let profile = &mut player_state.profile;
+        match self {
+            CapabilityChange::Monsters(monsters) => profile.monsters.extend(monsters),
+            CapabilityChange::Masters(masters) => profile.masters.extend(masters),
+            CapabilityChange::Teleports(teleports) => profile.teleports.extend(teleports),
+            CapabilityChange::Unlocks(unlocks) => {
+                for unlock in unlocks {
+                    match unlock {
+                        Unlock::TaskStorage => player_state.storage_unlocked = true,
+                        Unlock::Superiors => player_state.superiors_unlocked = true,
+                    }
+                }
+                profile.unlocks.extend(unlocks);
+            }
+            CapabilityChange::Quests(quests) => {
+                for quest in quests {
+                    if !player_state.quests_done.contains(quest) {
+                        player_state.quests_done.push(*quest);
+                    }
+                }
+            }
+            CapabilityChange::KillTime(monster, factor) => {
+                let current = player_state.kill_time_factor(*monster);
+                player_state
+                    .kill_time_factors
+                    .insert(*monster, current * factor);
+            }
+        }
+    }
+}
+
+fn parse_values<T: FromStr>(key: &str, values: &str) -> Result<Vec<T>, String> {
+    values
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("Unknown value '{}' for {}", value, key))
+        })
+        .collect()
+}
+
+impl FromStr for CapabilityChange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, values) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected a line on the form key = values, got '{}'", s))?;
+        let key = key.trim();
+        match key {
+            "monsters" => Ok(CapabilityChange::Monsters(parse_values(key, values)?)),
+            "masters" => Ok(CapabilityChange::Masters(parse_values(key, values)?)),
+            "teleports" => Ok(CapabilityChange::Teleports(
+                parse_values::<SupplyKind>(key, values)?
+                    .into_iter()
+                    .map(|supply| {
+                        Supplies::teleports()
+                            .contains(&supply)
+                            .then_some(supply)
+                            .ok_or_else(|| format!("Unknown teleport '{}'", supply))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            "unlocks" => Ok(CapabilityChange::Unlocks(parse_values(key, values)?)),
+            "quests" => Ok(CapabilityChange::Quests(parse_values(key, values)?)),
+            "kill_time" => {
+                let (monster, factor) = values.split_once(':').ok_or_else(|| {
+                    format!(
+                        "Expected kill_time = Monster:factor, got '{}'",
+                        values.trim()
+                    )
+                })?;
+                Ok(CapabilityChange::KillTime(
+                    monster
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Unknown monster '{}'", monster.trim()))?,
+                    factor
+                        .trim()
+                        .parse()
+                        .map_err(|err| format!("Invalid kill time factor '{}': {}", factor, err))?,
+                ))
+            }
+            _ => Err(format!(
+                "Unknown key '{}', expected one of monsters, masters, teleports, unlocks, quests or kill_time",
+                key
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CapabilityChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join<T: fmt::Display>(values: &[T]) -> String {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        match self {
+            CapabilityChange::Monsters(monsters) => write!(f, "monsters = {}", join(monsters)),
+            CapabilityChange::Masters(masters) => write!(f, "masters = {}", join(masters)),
+            CapabilityChange::Teleports(teleports) => write!(f, "teleports = {}", join(teleports)),
+            CapabilityChange::Unlocks(unlocks) => write!(f, "unlocks = {}", join(unlocks)),
+            CapabilityChange::Quests(quests) => write!(f, "quests = {}", join(quests)),
+            CapabilityChange::KillTime(monster, factor) => {
+                write!(f, "kill_time = {}:{}", monster, factor)
+            }
+        }
+    }
+}
+
+impl FromStr for AccountProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut profile = AccountProfile {
+            monsters: BTreeSet::new(),
+            masters: BTreeSet::new(),
+            teleports: BTreeSet::new(),
+            unlocks: BTreeSet::new(),
+            timeline: vec![],
+        };
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(timed_change) = line.strip_prefix("when ") {
+                let (goal, change) = timed_change.split_once(" then ").ok_or_else(|| {
+                    format!(
+                        "Expected a line on the form when <goal> then <change>, got '{}'",
+                        line
+                    )
+                })?;
+                profile
+                    .timeline
+                    .push((goal.trim().parse()?, change.parse()?));
+                continue;
+            }
+            match line.parse()? {
+                CapabilityChange::Monsters(monsters) => profile.monsters.extend(monsters),
+                CapabilityChange::Masters(masters) => profile.masters.extend(masters),
+                CapabilityChange::Teleports(teleports) => profile.teleports.extend(teleports),
+                CapabilityChange::Unlocks(unlocks) => profile.unlocks.extend(unlocks),
+                CapabilityChange::Quests(_) | CapabilityChange::KillTime(_, _) => {
+                    return Err(format!(
+                        "'{}' can only be used on the timeline, after when <goal> then",
+                        line
+                    ));
+                }
+            }
+        }
+        Ok(profile)
+    }
+}
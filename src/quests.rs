@@ -0,0 +1,92 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{
+    PlayerState, Quest, SimulationAction, SlayerState, Strategy, TaskState, costs::QUEST_TIME,
+    goals::Goal,
+};
+
+/// A quest to complete in the middle of a run. Can be parsed from strings like `PriestInPeril`,
+/// `PriestInPeril:1.5` or `PriestInPeril:1.5 when tasks=100`, where the number is the hours it takes,
+/// and the goal after `when` is written like `--goal`
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuestPlan {
+    pub quest: Quest,
+    pub time: Duration,
+    pub when: Option<Goal>, // Completed as soon as possible if there is no goal
+}
+
+impl FromStr for QuestPlan {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (quest, when) = match s.split_once(" when ") {
+            Some((quest, when)) => (quest, Some(when.trim().parse()?)),
+            None => (s, None),
+        };
+        let (quest, time) = match quest.split_once(':') {
+            Some((quest, hours)) => {
+                let hours: f32 = hours
+                    .trim()
+                    .parse()
+                    .map_err(|err| format!("Invalid quest hours '{}': {}", hours, err))?;
+                // Rejects negative, infinite and NaN hours, and hours too large for a duration
+                let time = Duration::try_from_secs_f32(hours * 3600.0).map_err(|_| {
+                    format!(
+                        "Quest hours must be a finite, non-negative number, got {}",
+                        hours
+                    )
+                })?;
+                (quest, time)
+            }
+            None => (quest, QUEST_TIME),
+        };
+        Ok(QuestPlan {
+            quest: quest
+                .trim()
+                .parse()
+                .map_err(|_| format!("Unknown quest '{}'", quest.trim()))?,
+            time,
+            when,
+        })
+    }
+}
+
+/// Wraps a strategy, and completes each planned quest between tasks once its goal is reached
+#[derive(Clone)]
+pub struct QuestStrategy<S> {
+    pub strategy: S,
+    pub plans: Vec<QuestPlan>,
+}
+
+impl<S: Strategy> Strategy for QuestStrategy<S> {
+    fn default_goal(&self) -> Goal {
+        self.strategy.default_goal()
+    }
+
+    fn should_terminate(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> Option<bool> {
+        self.strategy.should_terminate(slayer_state, player_state)
+    }
+
+    fn select_action(
+        &mut self,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) -> SimulationAction {
+        if !matches!(slayer_state.task_state, TaskState::Active(_))
+            && let Some(plan) = self.plans.iter().find(|plan| {
+                !player_state.quests_done.contains(&plan.quest)
+                    && plan
+                        .when
+                        .as_ref()
+                        .is_none_or(|goal| goal.is_reached(slayer_state, player_state))
+            })
+        {
+            return SimulationAction::CompleteQuest(plan.quest, plan.time);
+        }
+        self.strategy.select_action(slayer_state, player_state)
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    time::Duration,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    Args, Monster, SimulationConfig, SimulationStartPoint, SlayerData, SlayerMaster, Strategy,
+    SupplyKind, goals::GoalStrategy, quests::QuestStrategy, simulate_limpwurt,
+};
+
+/// An estimated cost that the simulation result may be sensitive to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Parameter {
+    KillTime(Monster), // Also scales the kill time of the monster's superior
+    TravelSteps(Monster),
+    MasterTravel(SlayerMaster),
+    SupplyGathering(SupplyKind, &'static str), // Supply and name of the source it is gathered from
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Parameter::KillTime(monster) => write!(f, "Kill time of {}", monster),
+            Parameter::TravelSteps(monster) => write!(f, "Travel steps to {}", monster),
+            Parameter::MasterTravel(master) => write!(f, "Travel to {}", master),
+            Parameter::SupplyGathering(supply, source) => {
+                write!(f, "Gathering {} ({})", supply, source)
+            }
+        }
+    }
+}
+
+/// Multipliers for the time taken by individual parameters. Parameters without a factor are not scaled
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostFactors(BTreeMap<Parameter, f32>);
+
+impl CostFactors {
+    pub fn single(parameter: Parameter, factor: f32) -> Self {
+        Self(BTreeMap::from([(parameter, factor)]))
+    }
+
+    pub fn insert(&mut self, parameter: Parameter, factor: f32) {
+        self.0.insert(parameter, factor);
+    }
+
+    pub fn get(&self, parameter: Parameter) -> Option<f32> {
+        self.0.get(&parameter).copied()
+    }
+
+    /// Scale `time`, the time taken by `parameter`
+    pub fn scale(&self, parameter: Parameter, time: Duration) -> Duration {
+        match self.get(parameter) {
+            Some(factor) => time.mul_f32(factor),
+            None => time,
+        }
+    }
+}
+
+/// Median hours of the successful runs. Run `i` uses the configured seed plus `i`, or `i` without a seed,
+/// so the same runs are simulated for every perturbation
+fn median_hours<S: Strategy + Clone + Send + Sync>(
+    start: &SimulationStartPoint,
+    config: &SimulationConfig,
+    strategy: &S,
+    num_simulations: u64,
+    cost_factors: CostFactors,
+) -> (f32, Vec<SlayerData>) {
+    let runs: Vec<SlayerData> = (0..num_simulations)
+        .into_par_iter()
+        .map(|seed| {
+            let config = SimulationConfig {
+                rng_seed: Some(config.rng_seed.unwrap_or(0).wrapping_add(seed)),
+                cost_factors: cost_factors.clone(),
+                ..config.clone()
+            };
+            simulate_limpwurt(start.clone(), &config, strategy.clone(), &mut [])
+        })
+        .filter(|(_, _, success)| *success)
+        .map(|(slayer_state, _, _)| slayer_state.slayer_data)
+        .collect();
+    let mut hours: Vec<f32> = runs
+        .iter()
+        .map(|run| run.time_spent().as_secs_f32() / 3600.0)
+        .collect();
+    hours.sort_by(f32::total_cmp);
+    (hours.get(hours.len() / 2).copied().unwrap_or(0.0), runs)
+}
+
+/// Perturb every parameter that is used in the baseline by ±`percent`,
+/// and rank them by their effect on the median hours
+pub fn run_sensitivity<S: Strategy + Clone + Send + Sync>(
+    start: SimulationStartPoint,
+    strategy: S,
+    args: Args,
+    percent: f32,
+) {
+    assert!(
+        percent > 0.0 && percent < 100.0,
+        "Perturbation must be between 0 and 100 percent"
+    );
+    let config = args.simulation_config();
+    let strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy,
+            plans: args.complete_quest.clone(),
+        },
+        args.goal.clone(),
+    );
+    println!(
+        "Simulating until {}, perturbing each parameter by ±{}%",
+        strategy.goal, percent
+    );
+
+    let (baseline, baseline_runs) = median_hours(
+        &start,
+        &config,
+        &strategy,
+        args.num_simulations,
+        CostFactors::default(),
+    );
+    println!("Baseline median: {:.2} hours", baseline);
+
+    // Parameters that are never used cannot change the result
+    let monsters: BTreeSet<Monster> = baseline_runs
+        .iter()
+        .flat_map(|run| run.total_kills.keys().map(|(_, monster)| *monster))
+        .collect();
+    let mut parameters: Vec<Parameter> = monsters
+        .iter()
+        .flat_map(|monster| {
+            [
+                Parameter::KillTime(*monster),
+                Parameter::TravelSteps(*monster),
+            ]
+        })
+        .collect();
+    let masters: BTreeSet<SlayerMaster> = baseline_runs
+        .iter()
+        .flat_map(|run| run.slayer_master_travels.keys().copied())
+        .collect();
+    parameters.extend(masters.into_iter().map(Parameter::MasterTravel));
+    let routes: BTreeSet<(SupplyKind, &'static str)> = baseline_runs
+        .iter()
+        .flat_map(|run| run.inventory.routes.keys().copied())
+        .collect();
+    parameters.extend(
+        routes
+            .into_iter()
+            .map(|(supply, source)| Parameter::SupplyGathering(supply, source)),
+    );
+
+    let mut effects: Vec<(Parameter, f32, f32)> = parameters
+        .into_iter()
+        .map(|parameter| {
+            let [lower, upper] = [1.0 - percent / 100.0, 1.0 + percent / 100.0].map(|factor| {
+                let cost_factors = CostFactors::single(parameter, factor);
+                median_hours(
+                    &start,
+                    &config,
+                    &strategy,
+                    args.num_simulations,
+                    cost_factors,
+                )
+                .0
+            });
+            (parameter, lower, upper)
+        })
+        .collect();
+    effects.sort_by(|a, b| (b.2 - b.1).abs().total_cmp(&(a.2 - a.1).abs()));
+
+    println!(
+        "{:84} {:>10} {:>10} {:>10}",
+        "Parameter",
+        format!("-{}%", percent),
+        format!("+{}%", percent),
+        "effect"
+    );
+    for (parameter, lower, upper) in effects {
+        println!(
+            "{:84} {:>9.2}h {:>9.2}h {:>9.2}h",
+            parameter.to_string(),
+            lower,
+            upper,
+            upper - lower
+        );
+    }
+}
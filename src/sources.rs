@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use crate::{Monster, PlayerState, Quest, SupplyKind, drops::Item, uncertainty::Uncertainty};
+
+/// Time to cut a gem, craft it into jewellery at a furnace and enchant it, TODO: Made up
+pub const GEM_PROCESSING_TIME: Duration = Duration::from_secs(20);
+
+/// Something the account needs before it can use a supply source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prerequisite {
+    Quest(Quest),
+    Kill(Monster),
+}
+
+impl Prerequisite {
+    pub fn is_met(self, player_state: &PlayerState) -> bool {
+        match self {
+            Prerequisite::Quest(quest) => player_state.quests_done.contains(&quest),
+            Prerequisite::Kill(monster) => player_state.can_kill(monster),
+        }
+    }
+}
+
+/// A way to gather a supply, like a monster drop, a crafting route or a shop
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupplySource {
+    pub supply: SupplyKind,
+    pub name: &'static str,
+    pub time_per_unit: Duration, // A unit of jewellery is a whole item, with all its charges
+    pub uncertainty: Option<Uncertainty>, // In seconds
+    pub prerequisites: &'static [Prerequisite],
+    pub gem: Option<Item>, // Uncut gem of each unit. A dropped gem only costs `GEM_PROCESSING_TIME` to turn into a unit
+}
+
+impl SupplySource {
+    pub fn is_available(&self, player_state: &PlayerState) -> bool {
+        self.prerequisites
+            .iter()
+            .all(|prerequisite| prerequisite.is_met(player_state))
+    }
+}
+
+/// Every known source of each supply
+pub const SUPPLY_SOURCES: &[SupplySource] = &[
+    SupplySource {
+        supply: SupplyKind::ExpeditiousBraceletCharges,
+        name: "Opal grind, crafted and enchanted",
+        time_per_unit: Duration::from_secs(91),
+        uncertainty: None,
+        prerequisites: &[],
+        gem: Some(Item::UncutOpal),
+    },
+    SupplySource {
+        supply: SupplyKind::BraceletOfSlaughterCharges,
+        name: "Red topaz from gem rocks",
+        time_per_unit: Duration::from_secs(23 * 60),
+        uncertainty: None,
+        prerequisites: &[],
+        gem: Some(Item::UncutRedTopaz),
+    },
+    SupplySource {
+        supply: SupplyKind::BraceletOfSlaughterCharges,
+        name: "Red topaz from Shilo Village gem rocks",
+        time_per_unit: Duration::from_secs(18 * 60), // TODO: Made up
+        uncertainty: None,
+        prerequisites: &[Prerequisite::Quest(Quest::ShiloVillage)],
+        gem: Some(Item::UncutRedTopaz),
+    },
+    SupplySource {
+        supply: SupplyKind::GamesNecklaceCharges,
+        name: "Crafted from emeralds",
+        time_per_unit: Duration::from_secs(66), // TODO: Made up
+        uncertainty: Some(Uncertainty::StdDev(24.0)),
+        prerequisites: &[],
+        gem: Some(Item::UncutEmerald),
+    },
+    SupplySource {
+        supply: SupplyKind::DuelingRingCharges,
+        name: "Crafted from emeralds",
+        time_per_unit: Duration::from_secs(66), // TODO: Made up
+        uncertainty: Some(Uncertainty::StdDev(24.0)),
+        prerequisites: &[],
+        gem: Some(Item::UncutEmerald),
+    },
+    SupplySource {
+        supply: SupplyKind::NecklaceOfPassageCharges,
+        name: "Spare jades from the opal grind",
+        time_per_unit: Duration::from_secs(24),
+        uncertainty: None,
+        prerequisites: &[],
+        gem: Some(Item::UncutJade),
+    },
+    SupplySource {
+        supply: SupplyKind::ChronicleCharges,
+        name: "Teleport cards from Diango",
+        time_per_unit: Duration::from_millis(500),
+        uncertainty: None,
+        prerequisites: &[],
+        gem: None,
+    },
+    SupplySource {
+        supply: SupplyKind::SkullSceptreCharges,
+        name: "Sceptre pieces from the Stronghold of Security",
+        time_per_unit: Duration::from_secs(2), // 15 seconds per sceptre, TODO: Made up
+        uncertainty: Some(Uncertainty::Range(1.0, 4.0)),
+        prerequisites: &[],
+        gem: None,
+    },
+    SupplySource {
+        supply: SupplyKind::GiantsoulAmuletCharges,
+        name: "Big bones from hill giants",
+        time_per_unit: Duration::from_secs(8),
+        uncertainty: None,
+        prerequisites: &[Prerequisite::Kill(Monster::HillGiants)],
+        gem: None,
+    },
+    SupplySource {
+        supply: SupplyKind::GiantsoulAmuletCharges,
+        name: "Big bones from moss giants",
+        time_per_unit: Duration::from_secs(11), // TODO: Made up
+        uncertainty: None,
+        prerequisites: &[Prerequisite::Kill(Monster::MossGiants)],
+        gem: None,
+    },
+    SupplySource {
+        supply: SupplyKind::GiantsoulAmuletCharges,
+        name: "Big bones from ogres",
+        time_per_unit: Duration::from_secs(15), // TODO: Made up
+        uncertainty: None,
+        // Ogres have no task data yet, so they never count as killable and this source is unused
+        prerequisites: &[Prerequisite::Kill(Monster::Ogres)],
+        gem: None,
+    },
+    SupplySource {
+        supply: SupplyKind::LawRunes,
+        name: "Rune shops",
+        time_per_unit: Duration::from_millis(500),
+        uncertainty: None,
+        prerequisites: &[],
+        gem: None,
+    },
+];
+
+/// All sources of `supply`, in catalogue order
+pub fn sources(supply: SupplyKind) -> impl Iterator<Item = &'static SupplySource> {
+    SUPPLY_SOURCES
+        .iter()
+        .filter(move |source| source.supply == supply)
+}
+
+/// Whether some source crafts its jewellery from `item`
+pub fn is_jewellery_gem(item: Item) -> bool {
+    SUPPLY_SOURCES.iter().any(|source| source.gem == Some(item))
+}
+
+/// The fastest source of `supply` that the account can use right now
+pub fn cheapest_source(supply: SupplyKind, player_state: &PlayerState) -> &'static SupplySource {
+    sources(supply)
+        .filter(|source| source.is_available(player_state))
+        .min_by_key(|source| source.time_per_unit)
+        .unwrap_or_else(|| panic!("No available source of {}", supply))
+}
@@ -0,0 +1,85 @@
+use std::{fmt::Write, fs, path::Path};
+
+/// The survival probability just after `time`, when at least one run got slayer-locked at `time`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurvivalPoint {
+    pub time: f64,
+    pub at_risk: usize, // Runs that had not ended before `time`
+    pub locks: usize,
+    pub survival: f64,
+}
+
+/// Kaplan–Meier estimate of the probability of not being slayer-locked yet.
+/// Each run is `(time, locked)`, where runs that reached their goal are censored at their end time
+pub fn kaplan_meier(runs: &[(f64, bool)]) -> Vec<SurvivalPoint> {
+    let mut runs = runs.to_vec();
+    runs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut curve = vec![];
+    let mut survival = 1.0;
+    let mut index = 0;
+    while index < runs.len() {
+        let time = runs[index].0;
+        let at_risk = runs.len() - index;
+        let mut locks = 0;
+        while index < runs.len() && runs[index].0 == time {
+            if runs[index].1 {
+                locks += 1;
+            }
+            index += 1;
+        }
+        if locks > 0 {
+            survival *= 1.0 - locks as f64 / at_risk as f64;
+            curve.push(SurvivalPoint {
+                time,
+                at_risk,
+                locks,
+                survival,
+            });
+        }
+    }
+    curve
+}
+
+/// The survival probability after `time`, where the curve is a step function
+pub fn survival_at(curve: &[SurvivalPoint], time: f64) -> f64 {
+    curve
+        .iter()
+        .take_while(|point| point.time <= time)
+        .last()
+        .map_or(1.0, |point| point.survival)
+}
+
+/// Print the survival probability after every `step` tasks or hours, until the last slayer lock
+pub fn print_survival_table(name: &str, curve: &[SurvivalPoint], step: f64) {
+    println!("Chance of not being slayer-locked after N {}:", name);
+    let max_time = curve.last().map_or(0.0, |point| point.time) + step;
+    let mut time = 0.0;
+    while time <= max_time {
+        println!(
+            "{:8.0} {}: {:.3}%",
+            time,
+            name,
+            100.0 * survival_at(curve, time)
+        );
+        time += step;
+    }
+}
+
+/// Write the survival curves over tasks and hours to a single CSV file
+pub fn write_survival_csv(path: &Path, task_curve: &[SurvivalPoint], hour_curve: &[SurvivalPoint]) {
+    let mut csv = String::from("axis,time,at_risk,locks,survival\n");
+    for (axis, curve) in [("tasks", task_curve), ("hours", hour_curve)] {
+        for point in curve {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                axis, point.time, point.at_risk, point.locks, point.survival
+            )
+            .unwrap();
+        }
+    }
+
+    fs::write(path, csv)
+        .unwrap_or_else(|err| panic!("Failed to write {}: {}", path.display(), err));
+}
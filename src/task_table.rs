@@ -0,0 +1,110 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+
+use crate::{Monster, SlayerData, SlayerMaster, trajectory::percentile};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskTableSort {
+    /// By slayer master, then monster
+    Name,
+    /// By mean tasks done, most first
+    Tasks,
+    /// By mean kills, most first
+    Kills,
+    /// By share of the total time, most first
+    Hours,
+}
+
+struct TaskRow {
+    master: SlayerMaster,
+    monster: Monster,
+    tasks: Vec<u64>, // Sorted, one per run
+    kills: Vec<u64>, // Sorted, one per run
+    hours_share: f64,
+}
+
+impl TaskRow {
+    fn mean(values: &[u64]) -> f64 {
+        values.iter().sum::<u64>() as f64 / values.len() as f64
+    }
+}
+
+/// Time spent on tasks from `monster`, including travel and superiors
+pub fn task_time(slayer_data: &SlayerData, master: SlayerMaster, monster: Monster) -> Duration {
+    let get = |times: &BTreeMap<(SlayerMaster, Monster), Duration>| {
+        times.get(&(master, monster)).copied().unwrap_or_default()
+    };
+    get(&slayer_data.task_travel_time)
+        + get(&slayer_data.kill_time)
+        + get(&slayer_data.superior_kill_time)
+}
+
+/// Print tasks and kills per slayer master and monster, aggregated over all runs
+pub fn print_task_table(runs: &[&SlayerData], sort: TaskTableSort) {
+    if runs.is_empty() {
+        return;
+    }
+    let total_time: Duration = runs.iter().map(|run| run.time_spent()).sum();
+
+    let tasks_done: BTreeSet<(SlayerMaster, Monster)> = runs
+        .iter()
+        .flat_map(|run| run.total_tasks_done.keys().copied())
+        .collect();
+    let mut rows: Vec<TaskRow> = tasks_done
+        .into_iter()
+        .map(|(master, monster)| {
+            let per_run = |counts: fn(&SlayerData) -> &BTreeMap<(SlayerMaster, Monster), u64>| {
+                let mut values: Vec<u64> = runs
+                    .iter()
+                    .map(|run| counts(run).get(&(master, monster)).copied().unwrap_or(0))
+                    .collect();
+                values.sort();
+                values
+            };
+            let time: Duration = runs.iter().map(|run| task_time(run, master, monster)).sum();
+            TaskRow {
+                master,
+                monster,
+                tasks: per_run(|run| &run.total_tasks_done),
+                kills: per_run(|run| &run.total_kills),
+                hours_share: time.as_secs_f64() / total_time.as_secs_f64(),
+            }
+        })
+        .collect();
+
+    match sort {
+        TaskTableSort::Name => {}
+        TaskTableSort::Tasks => {
+            rows.sort_by(|a, b| TaskRow::mean(&b.tasks).total_cmp(&TaskRow::mean(&a.tasks)))
+        }
+        TaskTableSort::Kills => {
+            rows.sort_by(|a, b| TaskRow::mean(&b.kills).total_cmp(&TaskRow::mean(&a.kills)))
+        }
+        TaskTableSort::Hours => rows.sort_by(|a, b| b.hours_share.total_cmp(&a.hours_share)),
+    }
+
+    println!(
+        "{:10} {:17} {:>8} {:>6} {:>6} {:>6} {:>9} {:>7} {:>7} {:>7} {:>6}",
+        "Master", "Monster", "tasks", "p10", "p50", "p90", "kills", "p10", "p50", "p90", "hours"
+    );
+    for row in rows {
+        println!(
+            "{:10} {:17} {:8.1} {:6} {:6} {:6} {:9.1} {:7} {:7} {:7} {:5.1}%",
+            row.master,
+            row.monster,
+            TaskRow::mean(&row.tasks),
+            percentile(&row.tasks, 0.1),
+            percentile(&row.tasks, 0.5),
+            percentile(&row.tasks, 0.9),
+            TaskRow::mean(&row.kills),
+            percentile(&row.kills, 0.1),
+            percentile(&row.kills, 0.5),
+            percentile(&row.kills, 0.9),
+            100.0 * row.hours_share
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    Location, Monster, PlayerState, Quest, SlayerData, SlayerMaster, SlayerState, TaskState, data,
+    Location, Monster, PlayerState, Quest, SimulationAction, SimulationConfig,
+    SimulationStartPoint, SlayerData, SlayerMaster, SlayerState, Strategy, TaskState,
+    TaskStorageStrategy, data,
 };
 
 #[test]
@@ -68,13 +70,18 @@ fn all_monster_are_assigned_test() {
     let slayer_master = SlayerMaster::Turael;
 
     for _ in 0..N {
-        slayer_state.new_assignment(&mut rng, slayer_master, &player);
+        slayer_state.new_assignment(
+            &mut rng,
+            slayer_master,
+            &player,
+            &SimulationConfig::default(),
+        );
 
         let TaskState::Active((monster, _, _)) = slayer_state.task_state else {
             panic!();
         };
         *frequency.entry(monster).or_insert(0) += 1;
-        slayer_state.complete_assignment(&mut rng, &mut player);
+        slayer_state.complete_assignment(&mut rng, &mut player, &SimulationConfig::default());
     }
 
     assert_eq!(slayer_state.task_streak, N);
@@ -94,3 +101,1763 @@ fn all_monster_are_assigned_test() {
         println!("{}: {:.2}%", monster, 100.0 * count as f32 / N as f32);
     }
 }
+
+/// Limpwurt after the slayer update
+fn test_start_point() -> SimulationStartPoint {
+    SimulationStartPoint {
+        slayer_exp: 1_308_538,
+        quests_done: vec![Quest::LostCity, Quest::PorcineOfInterest],
+        task_streak: 1,
+        points: 120,
+        task_state: TaskState::Active((Monster::Monkeys, SlayerMaster::Turael, 20)),
+        storage_unlocked: false,
+        superiors_unlocked: false,
+        combat_levels: None,
+        profile: Default::default(),
+        stock: Default::default(),
+    }
+}
+
+fn storage_test_state(task_state: TaskState) -> SlayerState {
+    SlayerState {
+        points: 0,
+        task_streak: 10,
+        task_state,
+        stored_task: None,
+        slayer_data: SlayerData::default(),
+        location: Location::SlayerMaster(SlayerMaster::Turael),
+    }
+}
+
+#[test]
+fn store_and_unstore_task_test() {
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], true);
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::Monkeys,
+        SlayerMaster::Turael,
+        20,
+    )));
+
+    slayer_state.store_task(&player);
+    assert!(slayer_state.task_state == TaskState::None);
+    assert_eq!(
+        slayer_state.stored_task,
+        Some((Monster::Monkeys, SlayerMaster::Turael, 20))
+    );
+    assert_eq!(slayer_state.slayer_data.num_stored_tasks, 1);
+
+    slayer_state.task_state = TaskState::Completed(Monster::Cows);
+    slayer_state.unstore_task();
+    assert!(
+        slayer_state.task_state == TaskState::Active((Monster::Monkeys, SlayerMaster::Turael, 20))
+    );
+    assert_eq!(slayer_state.stored_task, None);
+    assert_eq!(slayer_state.slayer_data.num_unstored_tasks, 1);
+}
+
+#[test]
+#[should_panic(expected = "storage is not unlocked")]
+fn store_task_without_storage_test() {
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::Monkeys,
+        SlayerMaster::Turael,
+        20,
+    )));
+    slayer_state.store_task(&player);
+}
+
+#[test]
+#[should_panic(expected = "one is already stored")]
+fn store_task_twice_test() {
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], true);
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::Monkeys,
+        SlayerMaster::Turael,
+        20,
+    )));
+    slayer_state.stored_task = Some((Monster::Rats, SlayerMaster::Turael, 15));
+    slayer_state.store_task(&player);
+}
+
+#[test]
+#[should_panic(expected = "another already active")]
+fn unstore_task_with_active_task_test() {
+    let mut slayer_state =
+        storage_test_state(TaskState::Active((Monster::Cows, SlayerMaster::Turael, 20)));
+    slayer_state.stored_task = Some((Monster::Monkeys, SlayerMaster::Turael, 20));
+    slayer_state.unstore_task();
+}
+
+#[test]
+fn task_storage_strategy_test() {
+    let mut strategy = TaskStorageStrategy::default();
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], true);
+
+    // Store an unkillable Turael task when we can't afford to skip it
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::Monkeys,
+        SlayerMaster::Turael,
+        20,
+    )));
+    assert_eq!(strategy.should_terminate(&slayer_state, &player), None);
+    assert!(matches!(
+        strategy.select_action(&slayer_state, &player),
+        SimulationAction::StoreTask
+    ));
+
+    // With the storage full, the same situation is a slayer lock
+    slayer_state.stored_task = Some((Monster::Monkeys, SlayerMaster::Turael, 20));
+    assert_eq!(
+        strategy.should_terminate(&slayer_state, &player),
+        Some(false)
+    );
+
+    // Unstore only once the skip is affordable
+    slayer_state.task_state = TaskState::Completed(Monster::Cows);
+    slayer_state.points = 30;
+    assert!(matches!(
+        strategy.select_action(&slayer_state, &player),
+        SimulationAction::NewAssignment(_)
+    ));
+    slayer_state.points = 200;
+    assert!(matches!(
+        strategy.select_action(&slayer_state, &player),
+        SimulationAction::UnstoreTask
+    ));
+
+    // Unlock storage once affordable
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    slayer_state.stored_task = None;
+    slayer_state.points = 600;
+    assert!(matches!(
+        strategy.select_action(&slayer_state, &player),
+        SimulationAction::UnlockTaskStorage
+    ));
+}
+
+#[test]
+fn superior_exp_test() {
+    let mut player = PlayerState {
+        superiors_unlocked: true,
+        ..PlayerState::new(1_308_538, vec![Quest::LostCity], false)
+    };
+    let mut slayer_state = storage_test_state(TaskState::None);
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        slayer_state.task_state =
+            TaskState::Active((Monster::Pyrefiends, SlayerMaster::Vannaka, 90));
+        slayer_state.complete_assignment(&mut rng, &mut player, &SimulationConfig::default());
+    }
+
+    let slayer_data = &slayer_state.slayer_data;
+    assert!(slayer_data.superior_kills.values().sum::<u64>() > 0);
+    let regular_kills = slayer_data.total_kills.values().sum::<u64>();
+    assert_eq!(
+        player.slayer_exp as u64 - 1_308_538,
+        regular_kills * Monster::Pyrefiends.slayer_exp() as u64 + slayer_data.superior_slayer_exp()
+    );
+    assert!(
+        slayer_data.time_spent()
+            > slayer_data
+                .superior_kill_time
+                .values()
+                .sum::<std::time::Duration>()
+    );
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+}
+
+#[test]
+fn superiors_locked_test() {
+    let mut player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    let mut slayer_state = storage_test_state(TaskState::None);
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        slayer_state.task_state =
+            TaskState::Active((Monster::Pyrefiends, SlayerMaster::Vannaka, 90));
+        slayer_state.complete_assignment(&mut rng, &mut player, &SimulationConfig::default());
+    }
+
+    assert!(slayer_state.slayer_data.superior_kills.is_empty());
+    assert!(slayer_state.slayer_data.drops.is_empty());
+}
+
+#[test]
+fn superiors_locked_by_default_test() {
+    use crate::goals::GoalStrategy;
+    use crate::{Args, MinimizeSlayerLockStrategy, simulate_limpwurt};
+    use clap::Parser;
+
+    // Superiors only spawn once the unlock is bought, even with a spawn on every kill
+    let config = SimulationConfig {
+        rng_seed: Some(13),
+        superior_spawn_rate: 1.0,
+        ..Default::default()
+    };
+    let run = |start: SimulationStartPoint| {
+        let strategy = GoalStrategy::new(
+            MinimizeSlayerLockStrategy::default(),
+            Some("tasks=100".parse().unwrap()),
+        );
+        simulate_limpwurt(start, &config, strategy, &mut [])
+            .0
+            .slayer_data
+    };
+    let locked = run(test_start_point());
+    assert!(locked.superior_kills.is_empty());
+    // Points never spent still count as the starting points, instead of a sentinel
+    assert!(locked.min_points <= test_start_point().points as u64);
+    let unlocked = run(SimulationStartPoint {
+        superiors_unlocked: true,
+        ..test_start_point()
+    });
+    assert!(!unlocked.superior_kills.is_empty());
+
+    // A spawn rate of 1 in 0 is rejected instead of becoming an infinite chance
+    assert!(Args::try_parse_from(["limp-slayer", "--superior-spawn-rate", "0"]).is_err());
+    assert!(Args::try_parse_from(["limp-slayer", "--superior-spawn-rate", "1"]).is_ok());
+}
+
+#[test]
+fn run_config_test() {
+    use crate::{Args, run_config};
+    use clap::Parser;
+
+    // Both arms of the task storage comparison get the same seed and sampled parameters for each run
+    let args = Args::parse_from([
+        "limp-slayer",
+        "--rng-seed",
+        "3",
+        "--sample-parameters",
+        "--default-uncertainty",
+        "10",
+    ]);
+    let config = args.simulation_config();
+    let [first, second] = [0, 0].map(|_| run_config(&config, &args, 4));
+    assert_eq!(first.rng_seed, second.rng_seed);
+    assert_eq!(first.cost_factors, second.cost_factors);
+    assert_ne!(first.cost_factors, config.cost_factors);
+    assert_ne!(run_config(&config, &args, 5).rng_seed, first.rng_seed);
+}
+
+#[test]
+fn drop_table_test() {
+    use crate::drops::{HILL_GIANT_DROPS, Item, SUPERIOR_UNIQUE_TABLE};
+    use std::collections::BTreeMap;
+
+    let mut rng = rand::rng();
+    const N: u64 = 100_000;
+
+    let mut drops = BTreeMap::new();
+    for _ in 0..N {
+        SUPERIOR_UNIQUE_TABLE.roll(&mut rng, &mut drops);
+    }
+    // The unique table always drops something
+    assert_eq!(drops.values().sum::<u64>(), N);
+    let heart_rate = drops[&Item::ImbuedHeart] as f32 / N as f32;
+    assert!((heart_rate - 2.0 / 16.0).abs() < 0.01, "{}", heart_rate);
+
+    let mut drops = BTreeMap::new();
+    for _ in 0..N {
+        HILL_GIANT_DROPS.roll(&mut rng, &mut drops);
+    }
+    assert_eq!(drops[&Item::BigBones], N);
+    let key_rate = drops[&Item::GiantKey] as f32 / N as f32;
+    assert!((key_rate - 1.0 / 128.0).abs() < 0.002, "{}", key_rate);
+}
+
+#[test]
+fn goal_parse_test() {
+    use crate::drops::Item;
+    use crate::goals::Goal;
+
+    assert_eq!("level=85".parse(), Ok(Goal::SlayerLevel(85)));
+    assert_eq!(
+        "item=imbuedheart:2".parse(),
+        Ok(Goal::Item(Item::ImbuedHeart, 2))
+    );
+    assert_eq!(
+        "points=500 and hours=100 or tasks=1000".parse(),
+        Ok(Goal::Any(vec![
+            Goal::All(vec![Goal::Points(500), Goal::Hours(100.0)]),
+            Goal::Tasks(1000)
+        ]))
+    );
+    assert!("points=lots".parse::<Goal>().is_err());
+    assert!("exp=1000".parse::<Goal>().is_err());
+}
+
+#[test]
+fn goal_is_reached_test() {
+    use crate::goals::Goal;
+
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    let mut slayer_state = storage_test_state(TaskState::None);
+    slayer_state.points = 500;
+
+    let goal: Goal = "points=500 and level=99 or level=75".parse().unwrap();
+    assert!(goal.is_reached(&slayer_state, &player));
+    let goal: Goal = "points=500 and level=99".parse().unwrap();
+    assert!(!goal.is_reached(&slayer_state, &player));
+}
+
+#[test]
+fn observer_test() {
+    use crate::goals::GoalStrategy;
+    use crate::observer::{SimulationObserver, SkipKind};
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+
+    #[derive(Default)]
+    struct CountingObserver {
+        assignments: u64,
+        completions: u64,
+        kills: u64,
+        point_skips: u64,
+        terminated: bool,
+    }
+
+    impl SimulationObserver for CountingObserver {
+        fn on_assignment(&mut self, _: SlayerMaster, _: Monster, _: u32, _: &SlayerState) {
+            self.assignments += 1;
+        }
+
+        fn on_completion(
+            &mut self,
+            _: SlayerMaster,
+            _: Monster,
+            kills: u64,
+            _: &SlayerState,
+            _: &PlayerState,
+        ) {
+            self.completions += 1;
+            self.kills += kills;
+        }
+
+        fn on_skip(&mut self, _: SlayerMaster, _: Monster, kind: SkipKind, _: &SlayerState) {
+            if kind == SkipKind::Points {
+                self.point_skips += 1;
+            }
+        }
+
+        fn on_termination(&mut self, _: bool, _: &SlayerState, _: &PlayerState) {
+            self.terminated = true;
+        }
+    }
+
+    let start = test_start_point();
+    let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+    let mut observer = CountingObserver::default();
+    let (slayer_state, _, _) = simulate_limpwurt(
+        start,
+        &SimulationConfig::default(),
+        strategy,
+        &mut [&mut observer],
+    );
+
+    let slayer_data = &slayer_state.slayer_data;
+    assert!(observer.terminated);
+    assert!(observer.point_skips > 0);
+    assert_eq!(
+        observer.assignments,
+        slayer_data.total_tasks_received.values().sum::<u64>()
+    );
+    assert_eq!(
+        observer.completions,
+        slayer_data.total_tasks_done.values().sum::<u64>()
+    );
+    assert_eq!(
+        observer.kills,
+        slayer_data.total_kills.values().sum::<u64>()
+    );
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+}
+
+#[test]
+fn trajectory_sampler_test() {
+    use crate::observer::SimulationObserver;
+    use crate::trajectory::{Sample, TrajectorySampler};
+    use std::time::Duration;
+
+    let hour = Duration::from_secs(3600);
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    let mut slayer_state = storage_test_state(TaskState::None);
+    slayer_state.points = 10;
+    let mut sampler = TrajectorySampler::new(hour);
+    sampler.on_start(&slayer_state, &player);
+    assert_eq!(sampler.samples.len(), 1);
+
+    // A task that spans several intervals only shows up in the interval it ends in
+    slayer_state.points = 20;
+    slayer_state.slayer_data.elapsed = hour * 7 / 2;
+    sampler.on_action(SimulationAction::CompleteTask, &slayer_state, &player);
+    let points: Vec<u32> = sampler.samples.iter().map(|sample| sample.points).collect();
+    assert_eq!(points, [10, 10, 10, 10]);
+
+    // An action that ends exactly on an interval is included in it
+    slayer_state.points = 30;
+    slayer_state.slayer_data.elapsed = hour * 4;
+    sampler.on_action(SimulationAction::PointSkip, &slayer_state, &player);
+    slayer_state.points = 40;
+    slayer_state.slayer_data.elapsed = hour * 9 / 2;
+    sampler.on_action(SimulationAction::CompleteTask, &slayer_state, &player);
+    sampler.on_termination(true, &slayer_state, &player);
+    let points: Vec<u32> = sampler.samples.iter().map(|sample| sample.points).collect();
+    assert_eq!(points, [10, 10, 10, 10, 30]);
+    assert_eq!(
+        sampler.final_sample,
+        Sample {
+            points: 40,
+            slayer_exp: 1_308_538,
+            slayer_level: player.slayer_level(),
+            task_streak: 10,
+        }
+    );
+}
+
+#[test]
+fn percentile_bands_test() {
+    use crate::observer::SimulationObserver;
+    use crate::trajectory::{TrajectorySampler, percentile, write_percentile_bands};
+    use std::fs;
+    use std::time::Duration;
+
+    assert_eq!(percentile(&[1, 2, 3, 4], 0.0), 1);
+    assert_eq!(percentile(&[1, 2, 3, 4], 0.5), 3);
+    assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0], 0.9), 4.0);
+
+    let hour = Duration::from_secs(3600);
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    // Runs that end after 1, 2 and 3 hours, with as many points as hours
+    let trajectories: Vec<TrajectorySampler> = (1..=3)
+        .map(|hours| {
+            let mut slayer_state = storage_test_state(TaskState::None);
+            let mut sampler = TrajectorySampler::new(hour);
+            sampler.on_start(&slayer_state, &player);
+            slayer_state.points = hours;
+            slayer_state.slayer_data.elapsed = hour * hours;
+            sampler.on_action(SimulationAction::CompleteTask, &slayer_state, &player);
+            sampler.on_termination(true, &slayer_state, &player);
+            sampler
+        })
+        .collect();
+
+    let path = std::env::temp_dir().join("limp_slayer_percentile_bands_test.csv");
+    write_percentile_bands(&path, &trajectories);
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0][..3], ["hours", "active_runs", "points_p10"]);
+    assert_eq!(rows[0].len(), 2 + 4 * 5);
+    assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+    // Ended runs keep their final state, so the bands never drop
+    let hours: Vec<&str> = rows[1..].iter().map(|row| row[0]).collect();
+    assert_eq!(hours, ["0.0", "1.0", "2.0", "3.0"]);
+    let active_runs: Vec<&str> = rows[1..].iter().map(|row| row[1]).collect();
+    assert_eq!(active_runs, ["3", "3", "2", "1"]);
+    let points: Vec<&[&str]> = rows[1..].iter().map(|row| &row[2..7]).collect();
+    assert_eq!(points[0], ["0", "0", "0", "0", "0"]);
+    assert_eq!(points[1], ["0", "0", "0", "1", "1"]);
+    assert_eq!(points[3], ["1", "2", "2", "3", "3"]);
+}
+
+#[test]
+fn milestone_test() {
+    use crate::goals::{Goal, GoalStrategy};
+    use crate::milestones::{Milestone, MilestoneTracker};
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+
+    let start = test_start_point();
+    // Runs that get slayer-locked before reaching the goal are retried
+    for _ in 0..20 {
+        let strategy = GoalStrategy::new(
+            MinimizeSlayerLockStrategy::default(),
+            Some(Goal::Points(500)),
+        );
+        let mut tracker = MilestoneTracker::new(75);
+        let (slayer_state, player_state, success) = simulate_limpwurt(
+            start.clone(),
+            &SimulationConfig::default(),
+            strategy,
+            &mut [&mut tracker],
+        );
+        if !success {
+            continue;
+        }
+
+        let slayer_data = &slayer_state.slayer_data;
+        assert_eq!(
+            tracker.reached[&Milestone::Points(500)],
+            (
+                slayer_data.total_tasks_done.values().sum::<u64>(),
+                slayer_data.time_spent()
+            )
+        );
+        assert!(!tracker.reached.contains_key(&Milestone::Points(1000)));
+        assert!(!tracker.reached.contains_key(&Milestone::SlayerLevel(75)));
+        let level_times: Vec<_> = (76..=player_state.slayer_level())
+            .map(|level| tracker.reached[&Milestone::SlayerLevel(level)])
+            .collect();
+        assert!(level_times.is_sorted());
+        return;
+    }
+    panic!("Every simulation got slayer-locked");
+}
+
+#[test]
+fn slayer_lock_test() {
+    use crate::failures::SlayerLock;
+
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::Monkeys,
+        SlayerMaster::Spria,
+        25,
+    )));
+    slayer_state.stored_task = Some((Monster::Bats, SlayerMaster::Turael, 15));
+    slayer_state
+        .slayer_data
+        .total_tasks_received
+        .insert((SlayerMaster::Spria, Monster::Monkeys), 3);
+    let lock = SlayerLock::new(&slayer_state).unwrap();
+    assert_eq!(lock.monster, Monster::Monkeys);
+    assert_eq!(lock.master, SlayerMaster::Spria);
+    assert_eq!(lock.task_streak, 10);
+    assert_eq!(lock.stored_task, Some(Monster::Bats));
+    assert_eq!(lock.tasks_received, 3);
+
+    let slayer_state = storage_test_state(TaskState::Completed(Monster::Monkeys));
+    assert!(SlayerLock::new(&slayer_state).is_none());
+}
+
+#[test]
+fn kaplan_meier_test() {
+    use crate::survival::{SurvivalPoint, kaplan_meier, survival_at};
+
+    // Runs that reached their goal are censored, and only count as at risk until they end
+    let runs = [
+        (4.0, false),
+        (1.0, true),
+        (3.0, true),
+        (2.0, false),
+        (3.0, false),
+    ];
+    let curve = kaplan_meier(&runs);
+    assert_eq!(
+        curve,
+        vec![
+            SurvivalPoint {
+                time: 1.0,
+                at_risk: 5,
+                locks: 1,
+                survival: 0.8,
+            },
+            SurvivalPoint {
+                time: 3.0,
+                at_risk: 3,
+                locks: 1,
+                survival: 0.8 * (1.0 - 1.0 / 3.0),
+            },
+        ]
+    );
+    assert_eq!(survival_at(&curve, 0.5), 1.0);
+    assert_eq!(survival_at(&curve, 2.0), 0.8);
+    assert_eq!(survival_at(&curve, 10.0), 0.8 * (1.0 - 1.0 / 3.0));
+}
+
+#[test]
+fn representative_run_test() {
+    use crate::metrics::{most_representative_run, run_metrics};
+
+    let metrics = |values: [f64; 3]| -> Vec<(String, f64)> {
+        ["hours", "points", "constant"]
+            .iter()
+            .zip(values)
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    };
+    let runs = vec![
+        metrics([10.0, 300.0, 1.0]),
+        metrics([50.0, 100.0, 1.0]),
+        metrics([20.0, 200.0, 1.0]),
+        metrics([30.0, 900.0, 1.0]),
+    ];
+    assert_eq!(most_representative_run(&runs), Some(2));
+    assert_eq!(most_representative_run(&[]), None);
+
+    let player_state = PlayerState::new(1_308_538, vec![], false);
+    let empty_run = run_metrics(&SlayerData::default(), &player_state);
+    let mut slayer_data = SlayerData::default();
+    slayer_data
+        .total_tasks_done
+        .insert((SlayerMaster::Spria, Monster::Bats), 4);
+    let run = run_metrics(&slayer_data, &player_state);
+    let names =
+        |run: &Vec<(String, f64)>| run.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&run), names(&empty_run));
+    assert!(run.contains(&("Spria_tasks_done".to_string(), 4.0)));
+
+    // The placeholder combat levels are never reported as a metric
+    assert!(!names(&run).contains(&"combat_level".to_string()));
+    let known_levels = PlayerState {
+        combat_levels_known: true,
+        ..player_state
+    };
+    let run = run_metrics(&slayer_data, &known_levels);
+    assert!(names(&run).contains(&"combat_level".to_string()));
+}
+
+#[test]
+fn task_time_test() {
+    use crate::sensitivity::{CostFactors, Parameter};
+    use crate::task_table::task_time;
+    use rand::{SeedableRng, rngs::SmallRng};
+    use std::time::Duration;
+
+    let mut player = PlayerState {
+        superiors_unlocked: true,
+        ..PlayerState::new(1_308_538, vec![Quest::LostCity], false)
+    };
+    let config = SimulationConfig {
+        superior_spawn_rate: 0.5,
+        ..Default::default()
+    };
+    let mut slayer_state = storage_test_state(TaskState::Active((
+        Monster::CaveCrawlers,
+        SlayerMaster::Spria,
+        40,
+    )));
+    slayer_state.complete_assignment(&mut rand::rng(), &mut player, &config);
+
+    let slayer_data = &slayer_state.slayer_data;
+    assert_eq!(
+        task_time(slayer_data, SlayerMaster::Spria, Monster::CaveCrawlers)
+            + slayer_data.gathering_time()
+            + slayer_data.inventory.worn.equip_time(),
+        slayer_data.time_spent()
+    );
+
+    // Kill time factors from the cost factors and the timeline also apply to the superiors of the task
+    let superior_kill_time = |cost_factors: CostFactors, timeline_factor: f32| {
+        let config = SimulationConfig {
+            superior_spawn_rate: 1.0,
+            cost_factors,
+            ..Default::default()
+        };
+        let mut slayer_state = storage_test_state(TaskState::Active((
+            Monster::CaveCrawlers,
+            SlayerMaster::Spria,
+            40,
+        )));
+        let mut player = player.clone();
+        player
+            .kill_time_factors
+            .insert(Monster::CaveCrawlers, timeline_factor);
+        slayer_state.complete_assignment(&mut SmallRng::seed_from_u64(5), &mut player, &config);
+        slayer_state
+            .slayer_data
+            .superior_kill_time
+            .values()
+            .sum::<Duration>()
+            .as_secs_f32()
+    };
+    let unscaled = superior_kill_time(CostFactors::default(), 1.0);
+    let scaled = superior_kill_time(
+        CostFactors::single(Parameter::KillTime(Monster::CaveCrawlers), 2.0),
+        1.0,
+    );
+    let timeline_scaled = superior_kill_time(CostFactors::default(), 0.5);
+    assert!(unscaled > 0.0);
+    assert!((scaled - 2.0 * unscaled).abs() < 0.01);
+    assert!((timeline_scaled - 0.5 * unscaled).abs() < 0.01);
+}
+
+#[test]
+fn sampled_time_breakdown_test() {
+    use crate::costs::{STORE_TASK_TIME, UNSTORE_TASK_TIME};
+    use crate::durations::{DurationDistribution, DurationModel};
+    use crate::goals::GoalStrategy;
+    use crate::metrics::run_metrics;
+    use crate::sensitivity::{CostFactors, Parameter};
+    use crate::task_table::task_time;
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+    use std::time::Duration;
+
+    // Sampled and scaled durations differ from the means, so the breakdown has to use the recorded times
+    let mut cost_factors = CostFactors::single(Parameter::KillTime(Monster::Cows), 1.3);
+    cost_factors.insert(Parameter::MasterTravel(SlayerMaster::Vannaka), 0.7);
+    let config = SimulationConfig {
+        rng_seed: Some(11),
+        cost_factors,
+        duration_model: DurationModel {
+            distribution: DurationDistribution::Gamma,
+            coefficient_of_variation: 0.5,
+        },
+        ..Default::default()
+    };
+    let start = SimulationStartPoint {
+        superiors_unlocked: true,
+        ..test_start_point()
+    };
+    let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+    let (slayer_state, player_state, _) = simulate_limpwurt(start, &config, strategy, &mut []);
+    let slayer_data = &slayer_state.slayer_data;
+    assert!(slayer_data.superior_kill_time.values().sum::<Duration>() > Duration::ZERO);
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+
+    let task_times: Duration = slayer_data
+        .total_tasks_done
+        .keys()
+        .map(|(master, monster)| task_time(slayer_data, *master, *monster))
+        .sum();
+    assert_eq!(
+        task_times
+            + slayer_data.master_travel_time.values().sum::<Duration>()
+            + STORE_TASK_TIME * slayer_data.num_stored_tasks as u32
+            + UNSTORE_TASK_TIME * slayer_data.num_unstored_tasks as u32
+            + slayer_data.quest_time
+            + slayer_data.gathering_time()
+            + slayer_data.inventory.worn.equip_time(),
+        slayer_data.time_spent()
+    );
+
+    let metrics = run_metrics(slayer_data, &player_state);
+    let hours = |name: &str| metrics.iter().find(|(metric, _)| metric == name).unwrap().1;
+    let breakdown: f64 = [
+        "master_travel_hours",
+        "task_travel_hours",
+        "kill_hours",
+        "superior_kill_hours",
+        "store_unstore_hours",
+        "quest_hours",
+        "equip_jewellery_hours",
+        "supplies_gathering_hours",
+    ]
+    .into_iter()
+    .map(hours)
+    .sum();
+    assert!((breakdown - hours("total_hours")).abs() < 1e-6);
+}
+
+#[test]
+fn seeded_perturbation_test() {
+    use crate::goals::GoalStrategy;
+    use crate::sensitivity::{CostFactors, Parameter};
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+
+    let config = SimulationConfig {
+        rng_seed: Some(7),
+        ..Default::default()
+    };
+    let run = |config: &SimulationConfig| {
+        let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+        simulate_limpwurt(test_start_point(), config, strategy, &mut [])
+            .0
+            .slayer_data
+    };
+
+    let baseline = run(&config);
+    let repeated = run(&config);
+    assert_eq!(baseline.total_kills, repeated.total_kills);
+    assert_eq!(baseline.time_spent(), repeated.time_spent());
+
+    // Perturbing a cost changes the time spent, but not the random draws
+    let (_, monster) = *baseline.total_kills.keys().next().unwrap();
+    let perturbed = run(&SimulationConfig {
+        cost_factors: CostFactors::single(Parameter::KillTime(monster), 2.0),
+        ..config.clone()
+    });
+    assert_eq!(baseline.total_kills, perturbed.total_kills);
+    assert!(perturbed.time_spent() > baseline.time_spent());
+
+    // Travelling to the slayer masters is perturbed too
+    let master = *baseline.slayer_master_travels.keys().next().unwrap();
+    let perturbed = run(&SimulationConfig {
+        cost_factors: CostFactors::single(Parameter::MasterTravel(master), 2.0),
+        ..config
+    });
+    assert_eq!(baseline.total_kills, perturbed.total_kills);
+    assert!(perturbed.time_spent() > baseline.time_spent());
+}
+
+#[test]
+fn parameter_uncertainty_test() {
+    use crate::sensitivity::Parameter;
+    use crate::uncertainty::{Uncertainty, sample_cost_factors};
+    use crate::{Args, SupplyKind};
+    use clap::Parser;
+    use rand::{SeedableRng, rngs::SmallRng};
+
+    let mut rng = rand::rng();
+    for _ in 0..100 {
+        let factor = Uncertainty::Range(1.0, 4.0).sample_factor(2.0, &mut rng);
+        assert!((0.5..=2.0).contains(&factor));
+        assert!(Uncertainty::StdDev(100.0).sample_factor(2.0, &mut rng) >= 0.0);
+    }
+    assert_eq!(Uncertainty::StdDev(0.0).sample_factor(2.0, &mut rng), 1.0);
+
+    // Without a default uncertainty, only costs with a known uncertainty are drawn
+    let cost_factors = sample_cost_factors(&mut rng, 0.0);
+    assert!(
+        cost_factors
+            .get(Parameter::KillTime(Monster::CaveCrawlers))
+            .is_some()
+    );
+    assert!(
+        cost_factors
+            .get(Parameter::KillTime(Monster::Bats))
+            .is_none()
+    );
+    let cost_factors = sample_cost_factors(&mut rng, 10.0);
+    assert!(
+        cost_factors
+            .get(Parameter::KillTime(Monster::Bats))
+            .is_some()
+    );
+    assert!(
+        cost_factors
+            .get(Parameter::MasterTravel(SlayerMaster::Spria))
+            .is_some()
+    );
+
+    // Drawing from a seeded generator gives the same costs every time
+    let draw = || sample_cost_factors(&mut SmallRng::seed_from_u64(3), 10.0);
+    assert_eq!(draw(), draw());
+    assert!(
+        cost_factors
+            .get(Parameter::SupplyGathering(
+                SupplyKind::LawRunes,
+                "Rune shops"
+            ))
+            .is_some()
+    );
+
+    // A negative or non-finite uncertainty is rejected instead of panicking while sampling
+    for uncertainty in ["-5", "inf", "NaN", "abc"] {
+        let args = ["limp-slayer", "--default-uncertainty", uncertainty];
+        assert!(Args::try_parse_from(args).is_err(), "{}", uncertainty);
+    }
+    let args = Args::try_parse_from(["limp-slayer", "--default-uncertainty", "20"]).unwrap();
+    assert_eq!(args.default_uncertainty, 20.0);
+}
+
+#[test]
+fn duration_model_test() {
+    use crate::durations::{DurationDistribution, DurationModel};
+    use std::time::Duration;
+
+    let mut rng = rand::rng();
+    let mean = Duration::from_secs(10);
+    let fixed = DurationModel {
+        distribution: DurationDistribution::Fixed,
+        coefficient_of_variation: 0.5,
+    };
+    assert_eq!(fixed.sample_sum(mean, 100, &mut rng), mean * 100);
+
+    for distribution in [DurationDistribution::Gamma, DurationDistribution::LogNormal] {
+        let model = DurationModel {
+            distribution,
+            coefficient_of_variation: 0.5,
+        };
+        assert_eq!(model.sample_sum(mean, 0, &mut rng), Duration::ZERO);
+        // Every sum of 100 kills has a standard deviation of 50 seconds, so the average of 400 sums is close to 1000 seconds
+        let total: Duration = (0..400)
+            .map(|_| model.sample_sum(mean, 100, &mut rng))
+            .sum();
+        let average = total.as_secs_f32() / 400.0;
+        assert!(
+            (980.0..1020.0).contains(&average),
+            "{:?} average was {}",
+            distribution,
+            average
+        );
+    }
+}
+
+#[test]
+fn combat_kill_time_test() {
+    use crate::Args;
+    use crate::combat::{self, CombatLevels, Equipment, KillTimeModel};
+    use clap::Parser;
+
+    let whip = Equipment {
+        attack_bonus: 82,
+        strength_bonus: 82,
+        attack_speed: 4,
+    };
+    let maxed = CombatLevels {
+        attack: 99,
+        strength: 99,
+        defence: 99,
+        hitpoints: 99,
+    };
+    assert_eq!(combat::max_hit(&maxed, &whip), 25);
+    assert_eq!(
+        combat::max_hit(&CombatLevels::default(), &Equipment::default()),
+        1
+    );
+
+    let hill_giant = Monster::HillGiants.task_data().unwrap();
+    let defence = hill_giant.defence.unwrap();
+    let weak = CombatLevels::default();
+    assert!(
+        combat::hit_chance(&maxed, &whip, &defence) > combat::hit_chance(&weak, &whip, &defence)
+    );
+    assert!(combat::kill_time(&maxed, &whip, &defence) < combat::kill_time(&weak, &whip, &defence));
+
+    // The measured kill time is used unless the combat model is chosen, and the monster has combat data
+    let player = PlayerState {
+        combat_levels: maxed,
+        ..PlayerState::new(1_308_538, vec![], false)
+    };
+    let mut config = SimulationConfig {
+        equipment: whip,
+        ..Default::default()
+    };
+    assert_eq!(
+        hill_giant.kill_time(&player, &config),
+        hill_giant.time_per_kill
+    );
+    config.kill_time_model = KillTimeModel::Combat;
+    assert_eq!(
+        hill_giant.kill_time(&player, &config),
+        combat::kill_time(&maxed, &whip, &defence)
+    );
+    let bats = Monster::Bats.task_data().unwrap();
+    assert_eq!(bats.kill_time(&player, &config), bats.time_per_kill);
+
+    // Superiors use the combat model too, once their defences are known
+    let mut superior = Monster::CaveCrawlers.task_data().unwrap().superior.unwrap();
+    assert_eq!(superior.kill_time(&player, &config), superior.time_per_kill);
+    superior.defence = Some(defence);
+    assert_eq!(
+        superior.kill_time(&player, &config),
+        combat::kill_time(&maxed, &whip, &defence)
+    );
+
+    // The combat model only runs on combat levels that are given
+    assert!(Args::try_parse_from(["limp-slayer", "--kill-time-model", "combat"]).is_err());
+    let args = Args::try_parse_from([
+        "limp-slayer",
+        "--kill-time-model",
+        "combat",
+        "--combat-levels",
+        "99, 99, 99, 99",
+    ])
+    .unwrap();
+    assert_eq!(args.combat_levels, Some(maxed));
+    assert!("99,99,99".parse::<CombatLevels>().is_err());
+    assert!("99,99,0,99".parse::<CombatLevels>().is_err());
+}
+
+#[test]
+fn combat_exp_test() {
+    use crate::combat::{CombatExp, CombatLevels, CombatSkill};
+
+    let mut player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    // Unknown combat levels never lock out a master
+    assert!(player.can_use_master(SlayerMaster::Vannaka));
+    player.combat_levels_known = true;
+    assert!(!player.can_use_master(SlayerMaster::Vannaka));
+    let mut slayer_state = storage_test_state(TaskState::None);
+    let mut rng = rand::rng();
+    let mut config = SimulationConfig::default();
+
+    // Hill giants have combat data, cows fall back to their slayer exp
+    let hill_giant_hitpoints = Monster::HillGiants
+        .task_data()
+        .unwrap()
+        .defence
+        .unwrap()
+        .hitpoints;
+    slayer_state.task_state = TaskState::Active((Monster::HillGiants, SlayerMaster::Turael, 30));
+    slayer_state.complete_assignment(&mut rng, &mut player, &config);
+    slayer_state.task_state = TaskState::Active((Monster::Cows, SlayerMaster::Turael, 30));
+    slayer_state.complete_assignment(&mut rng, &mut player, &config);
+
+    let damage = 30 * (hill_giant_hitpoints + Monster::Cows.slayer_exp());
+    let start = CombatExp::default();
+    assert_eq!(player.combat_exp.strength, start.strength + 4 * damage);
+    assert_eq!(
+        player.combat_exp.hitpoints,
+        start.hitpoints + 4 * damage / 3
+    );
+    assert_eq!(player.combat_exp.attack, start.attack);
+    assert_eq!(
+        player.combat_levels,
+        CombatLevels::from_exp(&player.combat_exp)
+    );
+    assert!(player.combat_levels.strength > 1);
+
+    // Enough kills unlock Vannaka
+    config.combat_skill = CombatSkill::Attack;
+    while !player.can_use_master(SlayerMaster::Vannaka) {
+        slayer_state.task_state =
+            TaskState::Active((Monster::HillGiants, SlayerMaster::Turael, 30));
+        slayer_state.complete_assignment(&mut rng, &mut player, &config);
+    }
+    assert!(player.combat_levels.combat_level() >= 40);
+    assert!(player.combat_levels.attack > 1);
+}
+
+#[test]
+fn account_profile_test() {
+    use crate::SupplyKind;
+    use crate::profile::{AccountProfile, Unlock};
+    use std::path::Path;
+
+    let limpwurt = AccountProfile::load(Path::new("profiles/limpwurt_2026.txt"));
+    assert_eq!(limpwurt, AccountProfile::limpwurt());
+
+    let profile: AccountProfile = "
+        # Cows and bats need teleports that are not available
+        monsters = Cows, HillGiants
+        monsters = bats
+        masters = Turael, Spria
+        teleports = giantsoul_amulet_charges
+        unlocks = Superiors
+    "
+    .parse()
+    .unwrap();
+    assert_eq!(profile.monsters.len(), 3);
+    assert!(profile.unlocks.contains(&Unlock::Superiors));
+    assert!(profile.can_kill(Monster::HillGiants));
+    assert!(!profile.can_kill(Monster::Cows));
+    assert!(!profile.can_kill(Monster::Zombies));
+
+    // Quests can add monsters without task data to the task pools, like banshees after Priest in Peril.
+    // Those can't be simulated, so they are unkillable even when the profile lists them
+    let banshee_profile: AccountProfile = "
+        monsters = Banshees, HillGiants
+        teleports = giantsoul_amulet_charges
+    "
+    .parse()
+    .unwrap();
+    assert!(Monster::Banshees.task_data().is_none());
+    assert!(!banshee_profile.can_kill(Monster::Banshees));
+    assert!(banshee_profile.can_kill(Monster::HillGiants));
+
+    let player_with = |profile: AccountProfile| PlayerState {
+        profile,
+        ..PlayerState::new(
+            1_308_538,
+            vec![Quest::LostCity, Quest::PorcineOfInterest],
+            false,
+        )
+    };
+    let player = player_with(profile.clone());
+    assert!(player.can_kill(Monster::HillGiants));
+    // Spria is only reachable with a necklace of passage, like monsters with their teleports
+    assert!(!player.can_use_master(SlayerMaster::Spria));
+    assert!(!player.can_use_master(SlayerMaster::Chaeldar));
+    let player = player_with(AccountProfile {
+        teleports: [SupplyKind::NecklaceOfPassageCharges].into(),
+        ..profile
+    });
+    assert!(player.can_use_master(SlayerMaster::Spria));
+    assert!(!player.can_use_master(SlayerMaster::Turael));
+    assert!(!player.can_use_master(SlayerMaster::Chaeldar));
+
+    // Masters in the profile still need their quests
+    let player = PlayerState::new(1_308_538, vec![Quest::LostCity], false);
+    assert!(!player.can_use_master(SlayerMaster::Spria));
+    assert!(player.can_use_master(SlayerMaster::Chaeldar));
+
+    assert!(
+        "monsters = Cows, Unicorns"
+            .parse::<AccountProfile>()
+            .is_err()
+    );
+    assert!(
+        "teleports = law_runes, bracelet_of_slaughter_charges"
+            .parse::<AccountProfile>()
+            .is_err()
+    );
+    assert!("combat = 3".parse::<AccountProfile>().is_err());
+}
+
+#[test]
+fn usable_masters_test() {
+    use crate::goals::{Goal, GoalStrategy};
+    use crate::profile::AccountProfile;
+    use crate::{MinimizeSlayerLockStrategy, SuperiorsStrategy, simulate_limpwurt};
+    use std::collections::BTreeSet;
+
+    let config = SimulationConfig {
+        rng_seed: Some(5),
+        ..Default::default()
+    };
+    let start_with_masters = |masters: &[SlayerMaster]| SimulationStartPoint {
+        profile: AccountProfile {
+            masters: masters.iter().copied().collect(),
+            ..AccountProfile::limpwurt()
+        },
+        ..test_start_point()
+    };
+    let masters_used = |slayer_state: &SlayerState| -> BTreeSet<SlayerMaster> {
+        slayer_state
+            .slayer_data
+            .total_tasks_received
+            .keys()
+            .map(|(master, _)| *master)
+            .collect()
+    };
+
+    // Without Spria, every strategy falls back to the masters in the profile
+    let without_spria = [SlayerMaster::Turael, SlayerMaster::Vannaka];
+    let strategy = GoalStrategy::new(
+        MinimizeSlayerLockStrategy::default(),
+        Some(Goal::Tasks(300)),
+    );
+    let (slayer_state, _, _) = simulate_limpwurt(
+        start_with_masters(&without_spria),
+        &config,
+        strategy,
+        &mut [],
+    );
+    assert!(!masters_used(&slayer_state).contains(&SlayerMaster::Spria));
+    let strategy = GoalStrategy::new(SuperiorsStrategy::default(), Some(Goal::Tasks(300)));
+    let (slayer_state, _, _) = simulate_limpwurt(
+        start_with_masters(&without_spria),
+        &config,
+        strategy,
+        &mut [],
+    );
+    assert!(!masters_used(&slayer_state).contains(&SlayerMaster::Spria));
+
+    // Without Turael, unwanted tasks can only be point-skipped
+    let strategy = GoalStrategy::new(TaskStorageStrategy::default(), Some(Goal::Tasks(300)));
+    let (slayer_state, _, _) = simulate_limpwurt(
+        start_with_masters(&[SlayerMaster::Spria]),
+        &config,
+        strategy,
+        &mut [],
+    );
+    assert_eq!(
+        masters_used(&slayer_state),
+        BTreeSet::from([SlayerMaster::Spria])
+    );
+
+    // Without any usable master, the run is slayer-locked right away
+    let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+    let (slayer_state, _, success) =
+        simulate_limpwurt(start_with_masters(&[]), &config, strategy, &mut []);
+    assert!(!success);
+    assert!(slayer_state.slayer_data.total_tasks_received.is_empty());
+}
+
+#[test]
+fn trace_test() {
+    use crate::goals::GoalStrategy;
+    use crate::quests::QuestStrategy;
+    use crate::trace::Tracer;
+    use crate::{Args, MinimizeSlayerLockStrategy, run_trace, simulate_limpwurt};
+    use clap::Parser;
+    use std::fs;
+
+    let config = SimulationConfig {
+        rng_seed: Some(17),
+        ..Default::default()
+    };
+    let path = std::env::temp_dir().join("limp_slayer_trace_test.txt");
+    let args = Args::parse_from(["limp-slayer", "--goal", "tasks=20", "trace"]);
+    run_trace(
+        test_start_point(),
+        &config,
+        MinimizeSlayerLockStrategy::default(),
+        args,
+        Some(path.clone()),
+    );
+    let trace = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // The same seed takes the same actions, with one line for each
+    let strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy: MinimizeSlayerLockStrategy::default(),
+            plans: vec![],
+        },
+        Some("tasks=20".parse().unwrap()),
+    );
+    let mut tracer = Tracer::default();
+    let (_, _, success) =
+        simulate_limpwurt(test_start_point(), &config, strategy, &mut [&mut tracer]);
+    assert!(success);
+    let lines: Vec<&str> = trace.lines().collect();
+    assert_eq!(lines.len(), tracer.events.len() + 2);
+    assert_eq!(lines[0], "Simulating until tasks=20");
+    for (line, event) in lines[1..].iter().zip(&tracer.events) {
+        assert!(line.contains(&format!("{:?}", event.action)));
+        assert_eq!(*line, event.to_string());
+    }
+    assert!(lines.last().unwrap().starts_with(&format!(
+        "Reached goal after {} actions",
+        tracer.events.len()
+    )));
+}
+
+#[test]
+fn capability_timeline_test() {
+    use crate::goals::GoalStrategy;
+    use crate::profile::{AccountProfile, CapabilityChange};
+    use crate::trace::Tracer;
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+
+    let timeline: AccountProfile = "
+        when tasks=3 then quests = PriestInPeril
+        when tasks=3 then kill_time = Trolls:0.5
+        when level=99 and hours=1000 then monsters = Monkeys
+    "
+    .parse()
+    .unwrap();
+    let config = SimulationConfig {
+        rng_seed: Some(3),
+        ..Default::default()
+    };
+    let start = SimulationStartPoint {
+        profile: AccountProfile {
+            timeline: timeline.timeline,
+            ..AccountProfile::limpwurt()
+        },
+        ..test_start_point()
+    };
+    let strategy = GoalStrategy::new(
+        MinimizeSlayerLockStrategy::default(),
+        Some("tasks=10".parse().unwrap()),
+    );
+    let mut tracer = Tracer::default();
+    let (_, player, success) = simulate_limpwurt(start, &config, strategy, &mut [&mut tracer]);
+    assert!(success);
+
+    // Only the changes that were reached are applied, once
+    assert!(player.quests_done.contains(&Quest::PriestInPeril));
+    assert_eq!(player.kill_time_factor(Monster::Trolls), 0.5);
+    assert!(!player.can_kill(Monster::Monkeys));
+    assert_eq!(player.profile.timeline.len(), 1);
+    let changes: Vec<_> = tracer
+        .events
+        .iter()
+        .flat_map(|event| event.changes.iter())
+        .collect();
+    assert_eq!(changes.len(), 2);
+
+    // Changes to the same cost stack
+    let mut player = PlayerState::new(1_308_538, vec![], false);
+    let change: CapabilityChange = "kill_time = Trolls:0.5".parse().unwrap();
+    change.apply(&mut player);
+    change.apply(&mut player);
+    assert_eq!(player.kill_time_factor(Monster::Trolls), 0.25);
+
+    assert!("quests = PriestInPeril".parse::<AccountProfile>().is_err());
+    assert!(
+        "when hours=10 monsters = Cows"
+            .parse::<AccountProfile>()
+            .is_err()
+    );
+    assert!(
+        "when hours=ten then monsters = Cows"
+            .parse::<AccountProfile>()
+            .is_err()
+    );
+    assert!(
+        "when hours=10 then kill_time = Trolls"
+            .parse::<AccountProfile>()
+            .is_err()
+    );
+}
+
+#[test]
+fn complete_quest_test() {
+    use crate::goals::GoalStrategy;
+    use crate::quests::{QuestPlan, QuestStrategy};
+    use crate::{MinimizeSlayerLockStrategy, simulate_limpwurt};
+    use std::time::Duration;
+
+    let plan: QuestPlan = "PriestInPeril:2 when tasks=3".parse().unwrap();
+    assert_eq!(plan.quest, Quest::PriestInPeril);
+    assert_eq!(plan.time, Duration::from_secs(7200));
+    assert!(plan.when.is_some());
+    let default: QuestPlan = "priestinperil".parse().unwrap();
+    assert_eq!(default.time, crate::costs::QUEST_TIME);
+    assert_eq!(default.when, None);
+    assert!("PriestInPeril:-1".parse::<QuestPlan>().is_err());
+    assert!("PriestInPeril:inf".parse::<QuestPlan>().is_err());
+    assert!("PriestInPeril:NaN".parse::<QuestPlan>().is_err());
+    assert!("PriestInPeril:1e30".parse::<QuestPlan>().is_err());
+    assert!("PriestInPeril:0".parse::<QuestPlan>().is_ok());
+    assert!("DragonSlayer2".parse::<QuestPlan>().is_err());
+
+    let start = test_start_point();
+    let config = SimulationConfig {
+        rng_seed: Some(5),
+        ..Default::default()
+    };
+    let turael_weight = total_weight_prop(
+        &PlayerState::new(1_308_538, start.quests_done.clone(), false),
+        SlayerMaster::Turael,
+    );
+    let strategy = GoalStrategy::new(
+        QuestStrategy {
+            strategy: MinimizeSlayerLockStrategy::default(),
+            plans: vec![plan],
+        },
+        Some("tasks=10".parse().unwrap()),
+    );
+    let (slayer_state, player, success) = simulate_limpwurt(start, &config, strategy, &mut []);
+    assert!(success);
+
+    // The quest is done once, and adds to Turael's assignments
+    assert!(player.quests_done.contains(&Quest::PriestInPeril));
+    assert!(total_weight_prop(&player, SlayerMaster::Turael) > turael_weight);
+    let slayer_data = &slayer_state.slayer_data;
+    assert_eq!(slayer_data.quest_time, Duration::from_secs(7200));
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+}
+
+#[test]
+fn inventory_restock_test() {
+    use crate::costs::RESTOCK_TRIP_TIME;
+    use crate::goals::GoalStrategy;
+    use crate::inventory::{Inventory, Restock, Stock};
+    use crate::trace::Tracer;
+    use crate::{MinimizeSlayerLockStrategy, Supplies, SupplyKind, simulate_limpwurt};
+    use std::time::Duration;
+
+    // Restock amounts are given like in `Supplies`, so jewellery charges become whole items
+    let amounts: Stock = "law_runes=10, games_necklace_charges=80".parse().unwrap();
+    assert_eq!(amounts.get(SupplyKind::LawRunes), 10);
+    assert_eq!(amounts.get(SupplyKind::GamesNecklaceCharges), 10);
+    let amounts: Stock = "games_necklace_charges=5".parse().unwrap();
+    assert_eq!(amounts.get(SupplyKind::GamesNecklaceCharges), 1);
+    assert!("law_runes=0".parse::<Stock>().is_err());
+    assert!("law_runes".parse::<Stock>().is_err());
+    assert!("coins=100".parse::<Stock>().is_err());
+
+    let mut inventory =
+        Inventory::with_restocks("law_runes=10, games_necklace_charges=80".parse().unwrap());
+    let law_runes = Supplies {
+        law_runes: 4,
+        ..Default::default()
+    };
+    let player_state = PlayerState::new(1_308_538, vec![], false);
+    // The stock starts empty, so the first use needs a session, which also covers the second
+    let session_time = RESTOCK_TRIP_TIME + Duration::from_millis(500) * 10;
+    assert_eq!(
+        inventory.take(
+            &law_runes,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        session_time
+    );
+    assert_eq!(
+        inventory.take(
+            &law_runes,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        Duration::ZERO
+    );
+    let elapsed = Duration::from_secs(3600);
+    let time = inventory.take(
+        &law_runes,
+        &player_state,
+        &SimulationConfig::default(),
+        elapsed,
+    );
+    assert_eq!(time, session_time);
+    assert_eq!(inventory.restocks.len(), 2);
+    assert_eq!(inventory.restocks[1].started_at, elapsed);
+    assert_eq!(inventory.stock.get(SupplyKind::LawRunes), 8);
+    assert_eq!(inventory.peak_stock.get(SupplyKind::LawRunes), 12);
+
+    // A use bigger than a session gathers several sessions at once. 200 charges are 25 necklaces
+    let many = Supplies {
+        games_necklace_charges: 200,
+        ..Default::default()
+    };
+    inventory.take(&many, &player_state, &SimulationConfig::default(), elapsed);
+    assert_eq!(inventory.restocks[2].amount, 30);
+    assert_eq!(inventory.stock.get(SupplyKind::GamesNecklaceCharges), 5);
+    assert_eq!(
+        inventory.gathering_time(),
+        inventory
+            .restocks
+            .iter()
+            .map(|restock| restock.time)
+            .sum::<Duration>()
+    );
+
+    let run = |restock_amounts: Stock| {
+        let config = SimulationConfig {
+            rng_seed: Some(11),
+            restock_amounts,
+            ..Default::default()
+        };
+        let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+        simulate_limpwurt(test_start_point(), &config, strategy, &mut [])
+            .0
+            .slayer_data
+    };
+    let slayer_data = run("law_runes=50, games_necklace_charges=40".parse().unwrap());
+    assert!(!slayer_data.inventory.restocks.is_empty());
+    // Supplies without a restock amount are still gathered when they are used
+    assert!(
+        slayer_data
+            .inventory
+            .restocks
+            .iter()
+            .all(|restock| restock.supply != SupplyKind::GiantsoulAmuletCharges)
+    );
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+    // Everything that was gathered or dropped is either used or still in stock
+    let gathered: u64 = slayer_data
+        .inventory
+        .restocks
+        .iter()
+        .filter(|restock| restock.supply == SupplyKind::LawRunes)
+        .map(|restock| restock.amount)
+        .sum();
+    assert_eq!(
+        gathered + slayer_data.inventory.byproducts.get(SupplyKind::LawRunes),
+        slayer_data.supplies_used.law_runes + slayer_data.inventory.stock.get(SupplyKind::LawRunes)
+    );
+    // Whole sessions are gathered, plus the time to get to each one
+    assert!(slayer_data.gathering_time() > run(Stock::default()).gathering_time());
+
+    // Runs can start with a stock, and every gathering session shows up in the trace
+    let config = SimulationConfig {
+        rng_seed: Some(11),
+        restock_amounts: "law_runes=50, games_necklace_charges=40".parse().unwrap(),
+        ..Default::default()
+    };
+    let start = SimulationStartPoint {
+        stock: "law_runes=100000".parse().unwrap(),
+        ..test_start_point()
+    };
+    let mut tracer = Tracer::default();
+    let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+    let (slayer_state, _, _) = simulate_limpwurt(start, &config, strategy, &mut [&mut tracer]);
+    let inventory = &slayer_state.slayer_data.inventory;
+    assert_eq!(inventory.peak_stock.get(SupplyKind::LawRunes), 100000);
+    assert!(
+        inventory
+            .restocks
+            .iter()
+            .all(|restock| restock.supply == SupplyKind::GamesNecklaceCharges)
+    );
+    let traced: Vec<&Restock> = tracer
+        .events
+        .iter()
+        .flat_map(|event| &event.restocks)
+        .collect();
+    assert!(!traced.is_empty());
+    assert_eq!(traced, inventory.restocks.iter().collect::<Vec<_>>());
+    let event = tracer
+        .events
+        .iter()
+        .find(|event| !event.restocks.is_empty())
+        .unwrap();
+    assert!(
+        event
+            .to_string()
+            .contains("gathered 5 units of games_necklace_charges")
+    );
+}
+
+#[test]
+fn restock_gathering_time_test() {
+    use crate::goals::GoalStrategy;
+    use crate::inventory::Stock;
+    use crate::{MinimizeSlayerLockStrategy, SupplyKind, simulate_limpwurt};
+    use strum::IntoEnumIterator;
+
+    // Gathering in sessions never saves time over gathering each supply exactly when it is used
+    let mut every_supply = Stock::default();
+    for supply in SupplyKind::iter() {
+        every_supply.add(supply, 1000);
+    }
+    for seed in 0..5 {
+        let run = |restock_amounts: Stock| {
+            let config = SimulationConfig {
+                rng_seed: Some(seed),
+                restock_amounts,
+                ..Default::default()
+            };
+            let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+            simulate_limpwurt(test_start_point(), &config, strategy, &mut [])
+                .0
+                .slayer_data
+        };
+        let just_in_time = run(Stock::default());
+        let restocked = run(every_supply.clone());
+        assert_eq!(restocked.supplies_used, just_in_time.supplies_used);
+        assert!(restocked.gathering_time() >= just_in_time.gathering_time());
+    }
+}
+
+#[test]
+fn jewellery_charges_test() {
+    use crate::inventory::Inventory;
+    use crate::jewellery::{EQUIP_TIME, Jewellery, WornJewellery};
+    use crate::{Supplies, SupplyKind};
+    use std::time::Duration;
+
+    let mut worn = WornJewellery::default();
+    // The first charge needs a new necklace, which crumbles after its 8th charge
+    assert_eq!(worn.use_charges(Jewellery::GamesNecklace, 1), 1);
+    assert_eq!(worn.use_charges(Jewellery::GamesNecklace, 7), 0);
+    assert_eq!(worn.use_charges(Jewellery::GamesNecklace, 1), 1);
+    assert_eq!(worn.use_charges(Jewellery::GamesNecklace, 20), 2);
+    assert_eq!(worn.use_charges(Jewellery::NecklaceOfPassage, 5), 1);
+    assert_eq!(worn.equip_time(), EQUIP_TIME * 5);
+
+    // Spare items are taken from the stock, one per crumbled item, so a session of 2 items lasts 10 charges
+    let mut inventory = Inventory::with_restocks("necklace_of_passage_charges=10".parse().unwrap());
+    let passage = Supplies {
+        necklace_of_passage_charges: 1,
+        ..Default::default()
+    };
+    let player_state = PlayerState::new(1_308_538, vec![], false);
+    for _ in 0..10 {
+        inventory.take(
+            &passage,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO,
+        );
+    }
+    assert_eq!(inventory.stock.get(SupplyKind::NecklaceOfPassageCharges), 0);
+    assert_eq!(inventory.restocks.len(), 1);
+    inventory.take(
+        &passage,
+        &player_state,
+        &SimulationConfig::default(),
+        Duration::ZERO,
+    );
+    assert_eq!(inventory.restocks.len(), 2);
+
+    // Gathering is counted per item
+    let supplies = Supplies {
+        bracelet_of_slaughter_charges: 31,
+        ..Default::default()
+    };
+    let mut inventory = Inventory::default();
+    assert_eq!(
+        inventory.take(
+            &supplies,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        Duration::from_secs(2 * 23 * 60) + EQUIP_TIME * 2
+    );
+}
+
+#[test]
+fn supply_sources_test() {
+    use crate::inventory::Inventory;
+    use crate::sensitivity::{CostFactors, Parameter};
+    use crate::sources::cheapest_source;
+    use crate::{Supplies, SupplyKind, profile::AccountProfile};
+    use std::time::Duration;
+
+    let mut player_state = PlayerState::new(1_308_538, vec![], false);
+    assert_eq!(
+        cheapest_source(SupplyKind::GiantsoulAmuletCharges, &player_state).name,
+        "Big bones from hill giants"
+    );
+    assert_eq!(
+        cheapest_source(SupplyKind::BraceletOfSlaughterCharges, &player_state).name,
+        "Red topaz from gem rocks"
+    );
+
+    // Sources become available as their prerequisites are met
+    player_state.quests_done.push(Quest::ShiloVillage);
+    assert_eq!(
+        cheapest_source(SupplyKind::BraceletOfSlaughterCharges, &player_state).name,
+        "Red topaz from Shilo Village gem rocks"
+    );
+    player_state.profile = AccountProfile {
+        monsters: [Monster::MossGiants].into(),
+        ..AccountProfile::limpwurt()
+    };
+    assert_eq!(
+        cheapest_source(SupplyKind::GiantsoulAmuletCharges, &player_state).name,
+        "Big bones from moss giants"
+    );
+    let source = cheapest_source(SupplyKind::GiantsoulAmuletCharges, &player_state);
+
+    // The time and units of each route are recorded
+    let mut inventory = Inventory::default();
+    let supplies = Supplies {
+        giantsoul_amulet_charges: 3,
+        ..Default::default()
+    };
+    inventory.take(
+        &supplies,
+        &player_state,
+        &SimulationConfig::default(),
+        Duration::ZERO,
+    );
+    inventory.take(
+        &supplies,
+        &player_state,
+        &SimulationConfig::default(),
+        Duration::ZERO,
+    );
+    assert_eq!(
+        inventory.routes[&(SupplyKind::GiantsoulAmuletCharges, source.name)],
+        (6, source.time_per_unit * 6)
+    );
+    assert_eq!(inventory.gathering_time(), source.time_per_unit * 6);
+
+    // Gathering is scaled by the factor of the source that is actually used
+    let time_with_factor = |source_name: &'static str| {
+        let config = SimulationConfig {
+            cost_factors: CostFactors::single(
+                Parameter::SupplyGathering(SupplyKind::GiantsoulAmuletCharges, source_name),
+                2.0,
+            ),
+            ..Default::default()
+        };
+        Inventory::default().take(&supplies, &player_state, &config, Duration::ZERO)
+    };
+    assert_eq!(time_with_factor(source.name), source.time_per_unit * 6);
+    assert_eq!(
+        time_with_factor("Big bones from hill giants"),
+        source.time_per_unit * 3
+    );
+}
+
+#[test]
+#[should_panic(expected = "No available source of giantsoul_amulet_charges")]
+fn no_supply_source_test() {
+    use crate::sources::cheapest_source;
+    use crate::{SupplyKind, profile::AccountProfile};
+
+    // Ogres can't be killed either, so big bones can't be gathered without hill or moss giants
+    let player_state = PlayerState {
+        profile: AccountProfile {
+            monsters: [Monster::Ogres].into(),
+            ..AccountProfile::limpwurt()
+        },
+        ..PlayerState::new(1_308_538, vec![], false)
+    };
+    cheapest_source(SupplyKind::GiantsoulAmuletCharges, &player_state);
+}
+
+#[test]
+fn byproduct_credit_test() {
+    use crate::drops::Item;
+    use crate::goals::GoalStrategy;
+    use crate::inventory::Inventory;
+    use crate::jewellery::EQUIP_TIME;
+    use crate::sources::GEM_PROCESSING_TIME;
+    use crate::{MinimizeSlayerLockStrategy, Supplies, SupplyKind, simulate_limpwurt};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    let player_state = PlayerState::new(1_308_538, vec![], false);
+    let mut inventory = Inventory::default();
+    inventory.credit(&BTreeMap::from([(Item::BigBones, 10), (Item::GiantKey, 1)]));
+    assert_eq!(inventory.stock.get(SupplyKind::GiantsoulAmuletCharges), 10);
+    assert_eq!(
+        inventory.byproducts.get(SupplyKind::GiantsoulAmuletCharges),
+        10
+    );
+
+    // Byproducts are used up before anything is gathered
+    let charges = |giantsoul_amulet_charges| Supplies {
+        giantsoul_amulet_charges,
+        ..Default::default()
+    };
+    assert_eq!(
+        inventory.take(
+            &charges(4),
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        Duration::ZERO
+    );
+    assert_eq!(
+        inventory.take(
+            &charges(8),
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        Duration::from_secs(8) * 2
+    );
+    assert_eq!(inventory.stock.get(SupplyKind::GiantsoulAmuletCharges), 0);
+
+    // Big bones from a hill giant task are credited during the run
+    let start = SimulationStartPoint {
+        task_state: TaskState::Active((Monster::HillGiants, SlayerMaster::Vannaka, 60)),
+        ..test_start_point()
+    };
+    let config = SimulationConfig {
+        rng_seed: Some(5),
+        ..Default::default()
+    };
+    let strategy = GoalStrategy::new(MinimizeSlayerLockStrategy::default(), None);
+    let slayer_data = simulate_limpwurt(start, &config, strategy, &mut [])
+        .0
+        .slayer_data;
+    let inventory = &slayer_data.inventory;
+    assert!(inventory.byproducts.get(SupplyKind::GiantsoulAmuletCharges) >= 60);
+    let gathered: u64 = inventory
+        .routes
+        .iter()
+        .filter(|((supply, _), _)| *supply == SupplyKind::GiantsoulAmuletCharges)
+        .map(|(_, (units, _))| units)
+        .sum();
+    assert_eq!(
+        inventory.byproducts.get(SupplyKind::GiantsoulAmuletCharges) + gathered,
+        slayer_data.supplies_used.giantsoul_amulet_charges
+            + inventory.stock.get(SupplyKind::GiantsoulAmuletCharges)
+    );
+    assert_eq!(slayer_data.time_spent(), slayer_data.recompute_time_spent());
+
+    // Dropped gems only save the time to get the gem, not to cut, craft and enchant it
+    let mut inventory = Inventory::default();
+    inventory.credit(&BTreeMap::from([
+        (Item::UncutEmerald, 2),
+        (Item::UncutRedTopaz, 1),
+    ]));
+    assert_eq!(inventory.stock.get(SupplyKind::GamesNecklaceCharges), 0);
+    let necklaces = Supplies {
+        games_necklace_charges: 24,
+        ..Default::default()
+    };
+    assert_eq!(
+        inventory.take(
+            &necklaces,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        GEM_PROCESSING_TIME * 2 + Duration::from_secs(66) + EQUIP_TIME * 3
+    );
+    let bracelet = Supplies {
+        bracelet_of_slaughter_charges: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        inventory.take(
+            &bracelet,
+            &player_state,
+            &SimulationConfig::default(),
+            Duration::ZERO
+        ),
+        GEM_PROCESSING_TIME + EQUIP_TIME
+    );
+    assert_eq!(inventory.gems_used[&Item::UncutEmerald], 2);
+    assert_eq!(inventory.gems_used[&Item::UncutRedTopaz], 1);
+    assert!(inventory.gems.values().all(|gems| *gems == 0));
+}
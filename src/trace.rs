@@ -0,0 +1,94 @@
+use std::{fmt, mem, time::Duration};
+
+use crate::{
+    Monster, PlayerState, SimulationAction, SlayerState, TaskState, drops::Item,
+    inventory::Restock, observer::SimulationObserver, profile::CapabilityChange,
+};
+
+/// The state after a single action in a simulation
+pub struct TraceEvent {
+    pub action: SimulationAction,
+    pub task_state: TaskState,
+    pub points: u32,
+    pub task_streak: u32,
+    pub slayer_exp: u32,
+    pub elapsed: Duration,
+    pub drops: Vec<(Item, u64)>, // Drops received from this action only
+    pub restocks: Vec<Restock>,  // Gathering sessions started by this action only
+    pub changes: Vec<CapabilityChange>, // Timeline changes applied just before this action
+}
+
+/// Records every action taken in a simulation
+#[derive(Default)]
+pub struct Tracer {
+    pub events: Vec<TraceEvent>,
+    drops: Vec<(Item, u64)>,        // Drops received since the last action
+    restocks: Vec<Restock>,         // Gathering sessions started since the last action
+    changes: Vec<CapabilityChange>, // Timeline changes applied since the last action
+}
+
+impl SimulationObserver for Tracer {
+    fn on_drop(&mut self, _monster: Monster, item: Item, amount: u64) {
+        self.drops.push((item, amount));
+    }
+
+    fn on_restock(&mut self, restock: &Restock, _slayer_state: &SlayerState) {
+        self.restocks.push(restock.clone());
+    }
+
+    fn on_capability_change(&mut self, change: &CapabilityChange, _slayer_state: &SlayerState) {
+        self.changes.push(change.clone());
+    }
+
+    fn on_action(
+        &mut self,
+        action: SimulationAction,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) {
+        self.events.push(TraceEvent {
+            action,
+            task_state: slayer_state.task_state,
+            points: slayer_state.points,
+            task_streak: slayer_state.task_streak,
+            slayer_exp: player_state.slayer_exp,
+            elapsed: slayer_state.slayer_data.time_spent(),
+            drops: mem::take(&mut self.drops),
+            restocks: mem::take(&mut self.restocks),
+            changes: mem::take(&mut self.changes),
+        });
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let task_state = match self.task_state {
+            TaskState::Active((_, _, amount)) => format!("{} ({})", self.task_state, amount),
+            _ => self.task_state.to_string(),
+        };
+        write!(
+            f,
+            "{:8.2}h {:24} {:48} {:5} points {:5} streak {:9} exp",
+            self.elapsed.as_secs_f32() / 3600.0,
+            format!("{:?}", self.action),
+            task_state,
+            self.points,
+            self.task_streak,
+            self.slayer_exp,
+        )?;
+        for (item, amount) in self.drops.iter() {
+            write!(f, ", {} x{}", item, amount)?;
+        }
+        for restock in self.restocks.iter() {
+            write!(
+                f,
+                ", gathered {} units of {}",
+                restock.amount, restock.supply
+            )?;
+        }
+        for change in self.changes.iter() {
+            write!(f, ", now {}", change)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+use crate::{PlayerState, SimulationAction, SlayerState, observer::SimulationObserver};
+
+pub const PERCENTILES: [f32; 5] = [0.1, 0.25, 0.5, 0.75, 0.9];
+
+/// Nearest-rank percentile of already sorted values, with `percentile` between 0 and 1
+pub fn percentile<T: Copy>(sorted_values: &[T], percentile: f32) -> T {
+    sorted_values[((sorted_values.len() - 1) as f32 * percentile).round() as usize]
+}
+const METRIC_NAMES: [&str; 4] = ["points", "slayer_exp", "slayer_level", "task_streak"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    pub points: u32,
+    pub slayer_exp: u32,
+    pub slayer_level: u8,
+    pub task_streak: u32,
+}
+
+impl Sample {
+    fn new(slayer_state: &SlayerState, player_state: &PlayerState) -> Self {
+        Self {
+            points: slayer_state.points,
+            slayer_exp: player_state.slayer_exp,
+            slayer_level: player_state.slayer_level(),
+            task_streak: slayer_state.task_streak,
+        }
+    }
+
+    /// The values of each metric, in the same order as `METRIC_NAMES`
+    fn metrics(&self) -> [f32; 4] {
+        [
+            self.points as f32,
+            self.slayer_exp as f32,
+            self.slayer_level as f32,
+            self.task_streak as f32,
+        ]
+    }
+}
+
+/// Samples the state of a single simulation every `interval`
+pub struct TrajectorySampler {
+    interval: Duration,
+    /// `samples[i]` is the state when `i * interval` time had passed
+    pub samples: Vec<Sample>,
+    /// The state when the simulation ended
+    pub final_sample: Sample,
+    /// The state after the latest action, which lasts until the next action is done
+    latest_sample: Sample,
+}
+
+impl TrajectorySampler {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            samples: vec![],
+            final_sample: Sample::default(),
+            latest_sample: Sample::default(),
+        }
+    }
+
+    fn sample_at(&self, index: usize) -> Sample {
+        self.samples
+            .get(index)
+            .copied()
+            .unwrap_or(self.final_sample)
+    }
+
+    /// Record the state of every interval up to `elapsed`, where `sample` is the state from `elapsed` on
+    fn advance(&mut self, elapsed: Duration, sample: Sample) {
+        // An action that spans several intervals only changes the state once it is done
+        while self.interval * (self.samples.len() as u32) < elapsed {
+            self.samples.push(self.latest_sample);
+        }
+        if self.interval * (self.samples.len() as u32) == elapsed {
+            self.samples.push(sample);
+        }
+        self.latest_sample = sample;
+    }
+}
+
+impl SimulationObserver for TrajectorySampler {
+    fn on_start(&mut self, slayer_state: &SlayerState, player_state: &PlayerState) {
+        self.latest_sample = Sample::new(slayer_state, player_state);
+        self.advance(
+            slayer_state.slayer_data.time_spent(),
+            Sample::new(slayer_state, player_state),
+        );
+    }
+
+    fn on_action(
+        &mut self,
+        _action: SimulationAction,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) {
+        self.advance(
+            slayer_state.slayer_data.time_spent(),
+            Sample::new(slayer_state, player_state),
+        );
+    }
+
+    fn on_termination(
+        &mut self,
+        _success: bool,
+        slayer_state: &SlayerState,
+        player_state: &PlayerState,
+    ) {
+        self.final_sample = Sample::new(slayer_state, player_state);
+    }
+}
+
+/// Write percentile bands over time for every sampled metric, as CSV.
+/// Simulations that have ended are included with their final state
+pub fn write_percentile_bands(path: &Path, trajectories: &[TrajectorySampler]) {
+    let Some(interval) = trajectories.first().map(|trajectory| trajectory.interval) else {
+        return;
+    };
+    let num_samples = trajectories
+        .iter()
+        .map(|trajectory| trajectory.samples.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut csv = String::from("hours,active_runs");
+    for name in METRIC_NAMES {
+        for percentile in PERCENTILES {
+            write!(csv, ",{}_p{}", name, (percentile * 100.0) as u32).unwrap();
+        }
+    }
+    csv.push('\n');
+
+    for index in 0..num_samples {
+        let active_runs = trajectories
+            .iter()
+            .filter(|trajectory| trajectory.samples.len() > index)
+            .count();
+        write!(
+            csv,
+            "{:.1},{}",
+            (interval * index as u32).as_secs_f32() / 3600.0,
+            active_runs
+        )
+        .unwrap();
+        for metric in 0..METRIC_NAMES.len() {
+            let mut values: Vec<f32> = trajectories
+                .iter()
+                .map(|trajectory| trajectory.sample_at(index).metrics()[metric])
+                .collect();
+            values.sort_by(f32::total_cmp);
+            for p in PERCENTILES {
+                write!(csv, ",{}", percentile(&values, p)).unwrap();
+            }
+        }
+        csv.push('\n');
+    }
+
+    fs::write(path, csv)
+        .unwrap_or_else(|err| panic!("Failed to write {}: {}", path.display(), err));
+}
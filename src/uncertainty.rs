@@ -0,0 +1,99 @@
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Monster, SlayerMaster,
+    sensitivity::{CostFactors, Parameter},
+    sources,
+};
+
+/// How uncertain an estimated cost is, in the same unit as the estimate.
+/// The actual value is drawn once per simulation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Uncertainty {
+    /// Normally distributed around the estimate, with this standard deviation
+    StdDev(f32),
+    /// Uniformly distributed between the two bounds, regardless of the estimate
+    Range(f32, f32),
+}
+
+impl Uncertainty {
+    /// Draw the actual value, as a factor of `estimate`. Never negative
+    pub fn sample_factor<R: Rng>(&self, estimate: f32, rng: &mut R) -> f32 {
+        if estimate <= 0.0 {
+            return 1.0;
+        }
+        let value = match *self {
+            Uncertainty::StdDev(std_dev) => Normal::new(estimate, std_dev)
+                .unwrap_or_else(|err| panic!("Invalid standard deviation {}: {}", std_dev, err))
+                .sample(rng),
+            Uncertainty::Range(min, max) => {
+                assert!(min <= max, "Invalid range {}..{}", min, max);
+                rng.random_range(min..=max)
+            }
+        };
+        value.max(0.0) / estimate
+    }
+}
+
+/// Parse a default uncertainty in percent, which must be a finite, non-negative number
+pub fn parse_default_uncertainty(value: &str) -> Result<f32, String> {
+    let percent: f32 = value
+        .trim()
+        .parse()
+        .map_err(|err| format!("Invalid uncertainty '{}': {}", value, err))?;
+    if !percent.is_finite() || percent < 0.0 {
+        return Err(format!(
+            "Uncertainty must be a finite, non-negative number, got {}",
+            percent
+        ));
+    }
+    Ok(percent)
+}
+
+/// Draw a factor for every estimated cost. Costs without an explicit uncertainty get a standard deviation
+/// of `default_uncertainty` percent of the estimate, or are left unchanged if that is zero
+pub fn sample_cost_factors<R: Rng>(rng: &mut R, default_uncertainty: f32) -> CostFactors {
+    let default = |estimate: f32| {
+        (default_uncertainty > 0.0)
+            .then(|| Uncertainty::StdDev(estimate * default_uncertainty / 100.0))
+    };
+    let mut cost_factors = CostFactors::default();
+    let mut sample = |parameter: Parameter, estimate: f32, uncertainty: Option<Uncertainty>| {
+        if let Some(uncertainty) = uncertainty.or_else(|| default(estimate)) {
+            cost_factors.insert(parameter, uncertainty.sample_factor(estimate, rng));
+        }
+    };
+
+    for monster in Monster::iter() {
+        let Some(task_data) = monster.task_data() else {
+            continue;
+        };
+        sample(
+            Parameter::KillTime(monster),
+            task_data.time_per_kill.as_secs_f32(),
+            task_data.time_per_kill_uncertainty,
+        );
+        sample(
+            Parameter::TravelSteps(monster),
+            task_data.travel_steps as f32,
+            None,
+        );
+    }
+    for master in SlayerMaster::iter() {
+        sample(
+            Parameter::MasterTravel(master),
+            master.travel_time().as_secs_f32(),
+            None,
+        );
+    }
+    for source in sources::SUPPLY_SOURCES {
+        sample(
+            Parameter::SupplyGathering(source.supply, source.name),
+            source.time_per_unit.as_secs_f32(),
+            source.uncertainty,
+        );
+    }
+    cost_factors
+}